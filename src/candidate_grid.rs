@@ -0,0 +1,338 @@
+use crate::{Board, Number, Square};
+
+impl Board {
+    /// Writes every square's candidates as one long line of `size` characters per square, such
+    /// as the 729 characters of a 9x9 board. Each square lists the numbers it could hold in their
+    /// own positions and a `.` in place of every other number, so a solved square lists only its
+    /// number. Squares are written row by row from the top left as the board is displayed, so the
+    /// first square is in the last row. Whether a number was given, or whether a square with one
+    /// candidate left is solved, is only kept by [`Board::to_candidate_grid`].
+    pub fn to_candidate_string(&self) -> String {
+        let size = self.size();
+
+        self.display_locations()
+            .flat_map(|location| {
+                let numbers = self.numbers_at(location);
+                Number::ALL[..size].iter().map(move |number| {
+                    if numbers.contains(number) {
+                        number.to_char()
+                    } else {
+                        '.'
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Writes every square's candidates as a grid the way they would be pencilled in, with the
+    /// boxes outlined. Solved squares show their number followed by `=` if it was given or `*`
+    /// if it was placed while solving, and squares with no candidates left show `0`. Rows are
+    /// written from the top as the board is displayed.
+    pub fn to_candidate_grid(&self) -> String {
+        let size = self.size();
+        let cells: Vec<String> = self
+            .display_locations()
+            .map(|location| {
+                let numbers = self.numbers_at(location);
+                if numbers.is_empty() {
+                    return "0".to_string();
+                }
+
+                let mut cell: String = numbers.iter().map(|number| number.to_char()).collect();
+                cell.extend(self.solved_mark(location));
+                cell
+            })
+            .collect();
+        let width = cells.iter().map(String::len).max().unwrap_or(1);
+
+        //          +----------------+----------------+----------------+
+        let border = format!(
+            "+{}\n",
+            format!("{}+", "-".repeat(self.box_width * (width + 1) + 1))
+                .repeat(size / self.box_width)
+        );
+
+        let mut grid = border.clone();
+        for (row, cells) in cells.chunks(size).enumerate() {
+            //      | 5    1238 23   | ...
+            for stack in cells.chunks(self.box_width) {
+                let stack: Vec<_> = stack.iter().map(|cell| format!("{cell:width$}")).collect();
+                grid.push_str(&format!("| {} ", stack.join(" ")));
+            }
+            grid.push_str("|\n");
+
+            if (row + 1) % self.box_height == 0 {
+                grid.push_str(&border);
+            }
+        }
+
+        grid
+    }
+
+    /// Replaces every square with the candidates written in `text`, which may be either a
+    /// candidate string from [`Board::to_candidate_string`] (where `0` may also stand for a
+    /// missing number) or a grid from [`Board::to_candidate_grid`]. Solved squares are placed
+    /// first: in a string, every square with one number written is solved and none are givens,
+    /// while a grid marks which squares are solved and given. Then every other square is
+    /// narrowed down to the candidates written for it on top of what the constraints allow.
+    /// Candidates ruled out this way stay ruled out when numbers are undone or constraints
+    /// change. Returns false without changing anything if `text` doesn't describe a board of
+    /// this size, its solved squares break the constraints, or the constraints rule out every
+    /// candidate written for a square.
+    pub fn read_candidates(&mut self, text: &str) -> bool {
+        let Some(squares) = self.parse_candidates(text) else {
+            return false;
+        };
+
+        let mut board = self.clone();
+        board.reset();
+        let locations: Vec<_> = board.display_locations().collect();
+        for (&location, square) in locations.iter().zip(&squares) {
+            let placed = match *square {
+                Square::Given(number) => board.try_give(number, location),
+                Square::Number(number) => board.try_collapse(number, location),
+                Square::Superposition(_) => true,
+            };
+            if !placed {
+                return false;
+            }
+        }
+        for (&location, square) in locations.iter().zip(&squares) {
+            if let Square::Superposition(_) = square {
+                let numbers = square.candidates();
+                if !board.eliminate(location, &numbers) && !numbers.is_empty() {
+                    return false;
+                }
+            }
+        }
+
+        *self = board;
+
+        true
+    }
+
+    /// Splits `text` into what is written for each square, in the order the squares are
+    /// displayed, or None if it doesn't hold the right number of squares.
+    fn parse_candidates(&self, text: &str) -> Option<Vec<Square>> {
+        let size = self.size();
+        let compact: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let is_string = compact.len() == size * size * size
+            && compact
+                .iter()
+                .all(|&c| ".0".contains(c) || Number::from_char(c).is_some());
+
+        let squares: Vec<Square> = if is_string {
+            compact
+                .chunks(size)
+                .map(|cell| {
+                    let numbers = cell
+                        .iter()
+                        .enumerate()
+                        .filter(|&(_, &c)| !".0".contains(c))
+                        .map(|(i, &c)| Number::from_char(c).filter(|number| number.index() == i))
+                        .collect::<Option<Vec<_>>>()?;
+                    match numbers.as_slice() {
+                        &[number] => Some(Square::Number(number)),
+                        _ => Some(Self::superposition(size, &numbers)),
+                    }
+                })
+                .collect::<Option<_>>()?
+        } else {
+            text.split(|c: char| c.is_whitespace() || "|+-.:".contains(c))
+                .filter(|token| !token.is_empty())
+                .map(|token| {
+                    if token == "0" {
+                        return Some(Self::superposition(size, &[]));
+                    }
+
+                    let (token, mark) = match token.strip_suffix(['=', '*']) {
+                        Some(number) => (number, token.chars().last()),
+                        None => (token, None),
+                    };
+                    let mut numbers = token
+                        .chars()
+                        .map(|c| Number::from_char(c).filter(|number| number.index() < size))
+                        .collect::<Option<Vec<_>>>()?;
+                    numbers.sort_unstable();
+                    numbers.dedup();
+                    match (mark, numbers.as_slice()) {
+                        (None, _) => Some(Self::superposition(size, &numbers)),
+                        (Some(mark), &[number]) => Some(Self::solved(number, mark)),
+                        (Some(_), _) => None,
+                    }
+                })
+                .collect::<Option<_>>()?
+        };
+
+        (squares.len() == size * size).then_some(squares)
+    }
+
+    /// An unsolved square holding only `numbers`.
+    fn superposition(size: usize, numbers: &[Number]) -> Square {
+        let mut square = Square::new(size);
+        for &number in &Number::ALL[..size] {
+            if !numbers.contains(&number) {
+                square.remove(number);
+            }
+        }
+
+        square
+    }
+
+    /// A square solved with `number`, given if it is marked with `=`.
+    fn solved(number: Number, mark: char) -> Square {
+        match mark {
+            '=' => Square::Given(number),
+            _ => Square::Number(number),
+        }
+    }
+
+    /// The mark written beside a solved square: `=` if its number was given and `*` if it was
+    /// placed while solving.
+    fn solved_mark(&self, location: (usize, usize)) -> Option<char> {
+        self.number(location)
+            .map(|_| if self.is_given(location) { '=' } else { '*' })
+    }
+
+    /// The numbers a square could hold: its collapsed number if it has one, or its candidates.
+    fn numbers_at(&self, location: (usize, usize)) -> Vec<Number> {
+        match self.number(location) {
+            Some(number) => vec![number],
+            None => self.candidates(location),
+        }
+    }
+
+    /// Every location in the order it is displayed: row by row from the top, left to right.
+    fn display_locations(&self) -> impl Iterator<Item = (usize, usize)> {
+        let size = self.size();
+
+        (0..size)
+            .rev()
+            .flat_map(move |row| (0..size).map(move |column| (row, column)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn candidate_strings_round_trip() {
+        let mut board = Board::default();
+        assert!(board.try_collapse(Number::Five, (8, 0)));
+        assert!(board.try_collapse(Number::Three, (4, 4)));
+
+        let text = board.to_candidate_string();
+        assert_eq!(729, text.len());
+        assert_eq!("....5....", &text[..9]);
+        assert_eq!("1234.6789", &text[9..18]);
+
+        let mut read = Board::default();
+        assert!(read.read_candidates(&text));
+        assert_eq!(board, read);
+        assert_eq!(text, read.to_candidate_string());
+
+        assert!(!read.read_candidates(&text[..728]));
+        // A 5 written alone in the second square clashes with the 5 in the first.
+        assert!(!read.read_candidates(&text.replacen("1234.6789", "....5....", 1)));
+    }
+
+    #[test]
+    fn candidate_grids_round_trip() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_collapse(Number::One, (3, 0)));
+        assert!(board.try_collapse(Number::Two, (0, 3)));
+
+        let grid = board.to_candidate_grid();
+        assert_eq!(
+            [
+                "+-----------+-----------+\n",
+                "| 1*   234  | 234  34   |\n",
+                "| 234  234  | 1234 134  |\n",
+                "+-----------+-----------+\n",
+                "| 234  1234 | 134  134  |\n",
+                "| 34   134  | 134  2*   |\n",
+                "+-----------+-----------+\n",
+            ]
+            .concat(),
+            grid
+        );
+
+        let mut read = Board::new(2, 2).unwrap();
+        assert!(read.read_candidates(&grid));
+        assert_eq!(board, read);
+    }
+
+    #[test]
+    fn candidates_are_narrowed_by_what_is_read() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::Region(vec![(0, 0), (3, 3)])));
+
+        let mut text = Board::new(2, 2).unwrap().to_candidate_string();
+        text.replace_range(..4, "12..");
+        assert!(board.read_candidates(&text));
+        assert_eq!(vec![Number::One, Number::Two], board.candidates((3, 0)));
+        assert!(board.try_collapse(Number::Four, (0, 0)));
+        assert!(!board.candidates((3, 3)).contains(&Number::Four));
+        assert_eq!(1, board.constraints().len() - 3);
+    }
+
+    #[test]
+    fn givens_and_naked_singles_round_trip() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+        assert!(board.try_collapse(Number::Two, (0, 3)));
+        // Narrow a1 down to a single candidate without solving it.
+        assert!(board.eliminate((0, 0), &[Number::Three]));
+
+        let grid = board.to_candidate_grid();
+        assert_eq!(
+            [
+                "+-----------+-----------+\n",
+                "| 1=   234  | 234  34   |\n",
+                "| 234  234  | 1234 134  |\n",
+                "+-----------+-----------+\n",
+                "| 234  1234 | 134  134  |\n",
+                "| 3    134  | 134  2*   |\n",
+                "+-----------+-----------+\n",
+            ]
+            .concat(),
+            grid
+        );
+
+        let mut read = Board::new(2, 2).unwrap();
+        assert!(read.read_candidates(&grid));
+        assert_eq!(board, read);
+        assert!(read.is_given((3, 0)));
+        assert_eq!(None, read.number((0, 0)));
+        assert_eq!(vec![Number::Three], read.candidates((0, 0)));
+
+        // A candidate string writes both the same way, so every lone number reads as placed.
+        let text = board.to_candidate_string();
+        assert_eq!("1...", &text[..4]);
+        assert_eq!("..3.", &text[48..52]);
+        let mut read = Board::new(2, 2).unwrap();
+        assert!(read.read_candidates(&text));
+        assert!(!read.is_given((3, 0)));
+        assert_eq!(Some(Number::One), read.number((3, 0)));
+        assert_eq!(Some(Number::Three), read.number((0, 0)));
+    }
+
+    #[test]
+    fn candidates_read_stay_ruled_out() {
+        let mut board = Board::new(2, 2).unwrap();
+        let mut text = board.to_candidate_string();
+        text.replace_range(..4, "12..");
+        assert!(board.read_candidates(&text));
+
+        assert!(board.try_collapse(Number::One, (0, 0)));
+        assert!(board.undo((0, 0)));
+        assert_eq!(vec![Number::One, Number::Two], board.candidates((3, 0)));
+
+        assert!(board.add_constraint(Constraint::AntiKing));
+        assert!(board.remove_constraint(&Constraint::AntiKing));
+        assert_eq!(vec![Number::One, Number::Two], board.candidates((3, 0)));
+    }
+}
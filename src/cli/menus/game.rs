@@ -1,17 +1,31 @@
-use superdoku::Number;
+use superdoku::{Mark, Number};
 
 pub enum Choice {
     Move(Number, (usize, usize)),
     Undo((usize, usize)),
     MoveRandom,
+    ToggleCandidates,
+    Pencil(PencilAction, Mark, Number, (usize, usize)),
+    FillPencilMarks(Mark),
     End,
 }
 
+pub enum PencilAction {
+    Add,
+    Remove,
+    Toggle,
+}
+
 pub fn menu() -> Choice {
     loop {
         println!("Move: m number position (m 1 a1)");
         println!("Undo: u position (u a1)");
         println!("Move Randomly: r");
+        println!("Show/Hide Candidates: c");
+        println!(
+            "Pencil Mark: p add|remove|toggle corner|center number position (p add center 1 a1)"
+        );
+        println!("Fill Pencil Marks From Candidates: f corner|center (f center)");
         println!("Quit: q");
         let mut choice = readln!("Make a choice: ");
 
@@ -19,6 +33,7 @@ pub fn menu() -> Choice {
 
         match choice.as_str() {
             "R" => return Choice::MoveRandom,
+            "C" => return Choice::ToggleCandidates,
             "Q" => return Choice::End,
             _ => {}
         }
@@ -34,6 +49,16 @@ pub fn menu() -> Choice {
                     return choice;
                 }
             }
+            Some(b'P') => {
+                if let Some(choice) = parse_pencil(choice[1..].trim()) {
+                    return choice;
+                }
+            }
+            Some(b'F') => {
+                if let Some(mark) = parse_mark(choice[1..].trim()) {
+                    return Choice::FillPencilMarks(mark);
+                }
+            }
             Some(_) | None => {}
         }
 
@@ -62,6 +87,35 @@ fn parse_undo(choice: &str) -> Option<Choice> {
     Some(Choice::Undo(parse_location(choices[0])?))
 }
 
+fn parse_pencil(choice: &str) -> Option<Choice> {
+    let choices: Vec<_> = choice.split(' ').collect();
+    if choices.len() != 4 {
+        return None;
+    }
+
+    let action = match choices[0] {
+        "A" | "ADD" => PencilAction::Add,
+        "R" | "REMOVE" => PencilAction::Remove,
+        "T" | "TOGGLE" => PencilAction::Toggle,
+        _ => return None,
+    };
+
+    Some(Choice::Pencil(
+        action,
+        parse_mark(choices[1])?,
+        parse_number(choices[2])?,
+        parse_location(choices[3])?,
+    ))
+}
+
+fn parse_mark(mark: &str) -> Option<Mark> {
+    Some(match mark {
+        "CORNER" => Mark::Corner,
+        "CENTER" => Mark::Center,
+        _ => return None,
+    })
+}
+
 fn parse_location(location: &str) -> Option<(usize, usize)> {
     let coordinates: Vec<_> = location.bytes().collect();
     if coordinates.len() != 2 {
@@ -1,11 +1,13 @@
 #[macro_use]
 mod io;
 mod menus;
+pub mod print;
 
-use superdoku::Board;
+use superdoku::{Board, Mark, PencilMarks};
 
 pub fn main() {
     io::clear();
+    let mut show_candidates = false;
     'main: loop {
         println!("Welcome to Superdoku!");
         let choice = menus::main::menu();
@@ -16,9 +18,14 @@ pub fn main() {
             menus::main::Choice::Quit => break 'main,
         }
         let mut board = Board::default();
+        let mut pencil_marks = PencilMarks::for_board(&board);
 
         while !board.is_solved() {
-            println!("{board}");
+            println!("{}", board.formatter().candidates(show_candidates));
+            if !pencil_marks.is_empty() {
+                println!("Pencil marks:");
+                println!("{}", board.formatter().pencil_marks(&pencil_marks));
+            }
             let choice = menus::game::menu();
 
             io::clear();
@@ -57,6 +64,51 @@ pub fn main() {
                         println!("Try undoing a move.")
                     }
                 },
+                menus::game::Choice::ToggleCandidates => {
+                    show_candidates = !show_candidates;
+                    if show_candidates {
+                        println!("Showing the candidates left in every square.");
+                    } else {
+                        println!("Hiding candidates.");
+                    }
+                }
+                menus::game::Choice::Pencil(action, mark, number, location) => {
+                    let place = format!(
+                        "the {} of {}",
+                        mark_name(mark),
+                        io::location_to_string(location)
+                    );
+                    match action {
+                        menus::game::PencilAction::Add => {
+                            if pencil_marks.add(mark, number, location) {
+                                println!("Pencilled {number} into {place}.");
+                            } else {
+                                println!("{number} was already pencilled into {place}.");
+                            }
+                        }
+                        menus::game::PencilAction::Remove => {
+                            if pencil_marks.remove(mark, number, location) {
+                                println!("Rubbed {number} out of {place}.");
+                            } else {
+                                println!("{number} was not pencilled into {place}.");
+                            }
+                        }
+                        menus::game::PencilAction::Toggle => {
+                            if pencil_marks.toggle(mark, number, location) {
+                                println!("Pencilled {number} into {place}.");
+                            } else {
+                                println!("Rubbed {number} out of {place}.");
+                            }
+                        }
+                    }
+                }
+                menus::game::Choice::FillPencilMarks(mark) => {
+                    pencil_marks.fill_from_candidates(mark, &board);
+                    println!(
+                        "Pencilled every square's candidates into its {}.",
+                        mark_name(mark)
+                    );
+                }
                 menus::game::Choice::End => continue 'main,
             }
         }
@@ -67,3 +119,10 @@ pub fn main() {
         println!("The board was solved!");
     }
 }
+
+fn mark_name(mark: Mark) -> &'static str {
+    match mark {
+        Mark::Corner => "corner",
+        Mark::Center => "center",
+    }
+}
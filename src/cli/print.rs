@@ -0,0 +1,80 @@
+use superdoku::Board;
+
+use std::path::{Path, PathBuf};
+
+/// Print puzzles onto PDF pages, such as a weekly puzzle sheet
+#[derive(clap::Args)]
+pub struct Args {
+    /// Files holding the puzzles to print, in .sdm, .sdk, .ss, HoDoKu or f-puzzles format
+    #[arg(required = true)]
+    pub puzzles: Vec<PathBuf>,
+
+    /// Where to write the PDF
+    #[arg(short, long, default_value = "puzzles.pdf")]
+    pub output: PathBuf,
+
+    /// How many puzzles to fit on each page
+    #[arg(short, long, default_value_t = 1, value_parser = parse_per_page)]
+    pub per_page: usize,
+
+    /// The title written above each puzzle, followed by its number
+    #[arg(short, long, default_value = "Puzzle")]
+    pub title: String,
+
+    /// Add pages with the solutions after the puzzles
+    #[arg(short, long)]
+    pub solutions: bool,
+}
+
+pub fn main(args: &Args) -> Result<(), String> {
+    let mut boards = Vec::new();
+    for path in &args.puzzles {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| format!("Couldn't read {}: {error}", path.display()))?;
+        let read = read_puzzles(path, &text)
+            .ok_or_else(|| format!("Couldn't find a puzzle in {}", path.display()))?;
+        boards.extend(read);
+    }
+
+    let puzzles: Vec<_> = boards
+        .into_iter()
+        .enumerate()
+        .map(|(i, board)| (format!("{} {}", args.title, i + 1), board))
+        .collect();
+    let pdf = Board::to_pdf(&puzzles, args.per_page, args.solutions)
+        .ok_or_else(|| format!("Can't fit {} puzzles on a page", args.per_page))?;
+    std::fs::write(&args.output, pdf)
+        .map_err(|error| format!("Couldn't write {}: {error}", args.output.display()))?;
+
+    println!(
+        "Printed {} puzzles to {}",
+        puzzles.len(),
+        args.output.display()
+    );
+
+    Ok(())
+}
+
+fn parse_per_page(text: &str) -> Result<usize, String> {
+    match text.parse() {
+        Ok(per_page @ (1 | 2 | 4 | 6)) => Ok(per_page),
+        _ => Err("must be 1, 2, 4 or 6".to_string()),
+    }
+}
+
+/// Reads every puzzle in a file, going by its extension to tell which format it is in.
+fn read_puzzles(path: &Path, text: &str) -> Option<Vec<Board>> {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase);
+
+    match extension.as_deref() {
+        Some("sdk") => Board::from_sdk(text).map(|board| vec![board]),
+        Some("ss") => Board::from_ss(text).map(|board| vec![board]),
+        Some("json") => Board::from_fpuzzles(text).map(|board| vec![board]),
+        _ => Board::from_sdm(text)
+            .or_else(|| Board::from_hodoku(text).map(|board| vec![board]))
+            .or_else(|| Board::from_fpuzzles(text).map(|board| vec![board])),
+    }
+}
@@ -0,0 +1,40 @@
+/// The ways a knight can move, as changes in row and column.
+pub(super) const KNIGHT_MOVES: [(isize, isize); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+
+/// The ways a king can move, as changes in row and column.
+pub(super) const KING_MOVES: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Every location on a board of the given size that is one of `moves` away from `location`.
+pub(super) fn reachable(
+    size: usize,
+    location: (usize, usize),
+    moves: &[(isize, isize)],
+) -> Vec<(usize, usize)> {
+    moves
+        .iter()
+        .filter_map(|&(rows, columns)| {
+            let row = location.0.checked_add_signed(rows)?;
+            let column = location.1.checked_add_signed(columns)?;
+
+            (row < size && column < size).then_some((row, column))
+        })
+        .collect()
+}
@@ -0,0 +1,326 @@
+use std::cell::OnceCell;
+
+use super::options;
+use crate::{Board, Constraint, Number};
+
+/// A marker drawn on the edge between two side-by-side squares, saying how their numbers relate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Dot {
+    /// A white Kropki dot: the numbers are consecutive.
+    White,
+    /// A black Kropki dot: one number is double the other.
+    Black,
+    /// An X: the numbers add up to 10.
+    X,
+    /// A V: the numbers add up to 5.
+    V,
+}
+impl Dot {
+    /// Whether two numbers on either side of this dot agree with it.
+    pub fn holds(&self, first: Number, second: Number) -> bool {
+        let (first, second) = (first.value(), second.value());
+
+        match self {
+            Self::White => first.abs_diff(second) == 1,
+            Self::Black => first == 2 * second || second == 2 * first,
+            Self::X => first + second == 10,
+            Self::V => first + second == 5,
+        }
+    }
+
+    /// Whether two dots come from the same set of puzzle rules, Kropki (white and black) or XV,
+    /// so that a pair without one kind of dot may still have the other.
+    fn is_same_family(self, other: Self) -> bool {
+        matches!(
+            (self, other),
+            (Self::White | Self::Black, Self::White | Self::Black)
+                | (Self::X | Self::V, Self::X | Self::V)
+        )
+    }
+}
+
+/// Removes every candidate on either side of a dot that no option on the other side agrees with.
+pub(super) fn dot_eliminations(
+    board: &Board,
+    dot: Dot,
+    locations: [(usize, usize); 2],
+) -> Vec<((usize, usize), Number)> {
+    pair_eliminations(board, locations, |first, second| dot.holds(first, second))
+}
+
+/// Removes every candidate on either side of an inequality that the other side leaves no room
+/// for, so that the number at `greater` stays above the one at `lesser`.
+pub(super) fn comparison_eliminations(
+    board: &Board,
+    greater: (usize, usize),
+    lesser: (usize, usize),
+) -> Vec<((usize, usize), Number)> {
+    pair_eliminations(board, [greater, lesser], |first, second| first > second)
+}
+
+/// Removes every candidate on either side of an undotted edge that would agree with `dot` whatever
+/// the other side holds, since every such edge has been given a dot.
+pub(super) fn all_given_eliminations(board: &Board, dot: Dot) -> Vec<((usize, usize), Number)> {
+    undotted_edges(board, dot)
+        .flat_map(|locations| {
+            pair_eliminations(board, locations, |first, second| !dot.holds(first, second))
+        })
+        .collect()
+}
+
+/// Whether the collapsed numbers on either side of a dot disagree with it.
+pub(super) fn is_dot_broken(board: &Board, dot: Dot, locations: [(usize, usize); 2]) -> bool {
+    match (board.number(locations[0]), board.number(locations[1])) {
+        (Some(first), Some(second)) => !dot.holds(first, second),
+        _ => false,
+    }
+}
+
+/// Whether the collapsed number at `greater` is not above the one at `lesser`.
+pub(super) fn is_comparison_broken(
+    board: &Board,
+    greater: (usize, usize),
+    lesser: (usize, usize),
+) -> bool {
+    match (board.number(greater), board.number(lesser)) {
+        (Some(greater), Some(lesser)) => greater <= lesser,
+        _ => false,
+    }
+}
+
+/// Whether the collapsed numbers on either side of an undotted edge agree with `dot`.
+pub(super) fn is_all_given_broken(board: &Board, dot: Dot) -> bool {
+    undotted_edges(board, dot).any(|locations| {
+        match (board.number(locations[0]), board.number(locations[1])) {
+            (Some(first), Some(second)) => dot.holds(first, second),
+            _ => false,
+        }
+    })
+}
+
+/// Whether two locations share an edge.
+pub(super) fn are_adjacent(first: (usize, usize), second: (usize, usize)) -> bool {
+    first.0.abs_diff(second.0) + first.1.abs_diff(second.1) == 1
+}
+
+/// Every pair of side-by-side squares that has no dot from the same family as `dot` between them.
+fn undotted_edges(board: &Board, dot: Dot) -> impl Iterator<Item = [(usize, usize); 2]> + '_ {
+    let size = board.size();
+    let dotted: Vec<_> = board
+        .constraints()
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::Dot {
+                dot: other,
+                locations,
+            } if dot.is_same_family(*other) => {
+                let mut locations = *locations;
+                locations.sort_unstable();
+                Some(locations)
+            }
+            _ => None,
+        })
+        .collect();
+
+    (0..size)
+        .flat_map(move |row| (0..size).map(move |column| (row, column)))
+        .flat_map(move |location| {
+            [(location.0 + 1, location.1), (location.0, location.1 + 1)]
+                .into_iter()
+                .filter(move |other| other.0 < size && other.1 < size)
+                .map(move |other| [location, other])
+        })
+        .filter(move |locations| !dotted.contains(locations))
+}
+
+/// Removes every candidate on either side of a pair of squares that no option on the other side
+/// can go with under `allowed`. The same number only goes with itself when the squares are not
+/// neighbors.
+fn pair_eliminations(
+    board: &Board,
+    locations: [(usize, usize); 2],
+    allowed: impl Fn(Number, Number) -> bool,
+) -> Vec<((usize, usize), Number)> {
+    let neighbors = OnceCell::new();
+    let fits = |first: Number, second: Number| {
+        allowed(first, second)
+            && (first != second
+                || !*neighbors.get_or_init(|| {
+                    board
+                        .find_neighbor_locations(locations[0])
+                        .contains(&locations[1])
+                }))
+    };
+
+    let options = locations.map(|location| options(board, location));
+    let mut eliminations = Vec::new();
+    for (side, &location) in locations.iter().enumerate() {
+        let others = &options[1 - side];
+        eliminations.extend(
+            board
+                .candidates(location)
+                .into_iter()
+                .filter(|&number| {
+                    !others.iter().any(|&other| {
+                        if side == 0 {
+                            fits(number, other)
+                        } else {
+                            fits(other, number)
+                        }
+                    })
+                })
+                .map(|number| (location, number)),
+        );
+    }
+
+    eliminations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dots_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::Black,
+            locations: [(0, 0), (0, 1)],
+        }));
+        assert_eq!(
+            vec![
+                Number::One,
+                Number::Two,
+                Number::Three,
+                Number::Four,
+                Number::Six,
+                Number::Eight,
+            ],
+            board.candidates((0, 0))
+        );
+
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::V,
+            locations: [(0, 1), (1, 1)],
+        }));
+        assert_eq!(
+            vec![Number::One, Number::Two, Number::Three, Number::Four],
+            board.candidates((0, 1))
+        );
+
+        assert!(board.try_collapse(Number::Three, (0, 1)));
+        assert_eq!(vec![Number::Six], board.candidates((0, 0)));
+        assert_eq!(vec![Number::Two], board.candidates((1, 1)));
+
+        assert!(!board.add_constraint(Constraint::Dot {
+            dot: Dot::X,
+            locations: [(0, 0), (2, 2)],
+        }));
+    }
+
+    #[test]
+    fn all_given_rules_out_undotted_pairs() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::White,
+            locations: [(0, 0), (0, 1)],
+        }));
+        assert!(board.add_constraint(Constraint::AllGiven(Dot::White)));
+
+        assert!(board.try_collapse(Number::Five, (1, 1)));
+        assert!(!board.candidates((1, 0)).contains(&Number::Four));
+        assert!(!board.candidates((1, 2)).contains(&Number::Six));
+        assert!(board.candidates((1, 2)).contains(&Number::Seven));
+
+        assert!(board.try_collapse(Number::Four, (0, 0)));
+        assert_eq!(vec![Number::Three], board.candidates((0, 1)));
+    }
+
+    #[test]
+    fn all_given_only_counts_dots_of_its_family() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::White,
+            locations: [(0, 0), (0, 1)],
+        }));
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::Black,
+            locations: [(3, 3), (3, 4)],
+        }));
+        assert!(board.add_constraint(Constraint::AllGiven(Dot::V)));
+        assert!(board.add_constraint(Constraint::AllGiven(Dot::White)));
+
+        // The white dot does not stand in for a V, so 2 and 3 cannot sit on either side of it.
+        assert!(board.try_collapse(Number::Two, (0, 0)));
+        assert_eq!(vec![Number::One], board.candidates((0, 1)));
+
+        // The black dot does stand in for a white one, so 1 and 2 may still sit on either side.
+        assert!(board.try_collapse(Number::One, (3, 3)));
+        assert_eq!(vec![Number::Two], board.candidates((3, 4)));
+    }
+
+    #[test]
+    fn comparisons_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::GreaterThan {
+            greater: (0, 0),
+            lesser: (0, 1),
+        }));
+        assert!(board.add_constraint(Constraint::GreaterThan {
+            greater: (0, 1),
+            lesser: (1, 1),
+        }));
+        assert_eq!(Number::ALL[2..9].to_vec(), board.candidates((0, 0)));
+        assert_eq!(Number::ALL[1..8].to_vec(), board.candidates((0, 1)));
+        assert_eq!(Number::ALL[..7].to_vec(), board.candidates((1, 1)));
+
+        assert!(board.try_collapse(Number::Three, (0, 1)));
+        assert_eq!(Number::ALL[3..9].to_vec(), board.candidates((0, 0)));
+        assert_eq!(vec![Number::One, Number::Two], board.candidates((1, 1)));
+    }
+
+    #[test]
+    fn dot_puzzles_solve() {
+        let mut board = Board::new(2, 2).unwrap();
+        for locations in [
+            [(0, 0), (0, 1)],
+            [(0, 1), (0, 2)],
+            [(0, 2), (0, 3)],
+            [(1, 0), (1, 1)],
+            [(1, 2), (1, 3)],
+            [(2, 0), (2, 1)],
+            [(2, 2), (2, 3)],
+            [(3, 0), (3, 1)],
+            [(3, 1), (3, 2)],
+            [(3, 2), (3, 3)],
+            [(1, 0), (2, 0)],
+            [(1, 3), (2, 3)],
+        ] {
+            assert!(board.add_constraint(Constraint::Dot {
+                dot: Dot::White,
+                locations,
+            }));
+        }
+        assert!(board.add_constraint(Constraint::AllGiven(Dot::White)));
+        // Swapping every number n for 5 - n keeps the same dots, so one number is given.
+        assert!(board.try_give(Number::One, (0, 0)));
+        assert_eq!(1, board.count_solutions(2));
+
+        let solved = board.solve().unwrap();
+        for (row, values) in [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]]
+            .iter()
+            .enumerate()
+        {
+            for (column, &value) in values.iter().enumerate() {
+                assert_eq!(Some(Number::ALL[value - 1]), solved.number((row, column)));
+            }
+        }
+    }
+}
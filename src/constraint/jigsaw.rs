@@ -0,0 +1,143 @@
+use crate::{Board, Constraint};
+
+use std::collections::VecDeque;
+
+impl Board {
+    /// Replaces the boxes with irregular regions, as in Jigsaw Sudoku. Returns false without
+    /// changing anything unless the regions tile the board: there must be `size` of them, each
+    /// made of `size` orthogonally connected squares, together covering every square once.
+    pub fn replace_boxes(&mut self, mut regions: Vec<Vec<(usize, usize)>>) -> bool {
+        if !tiles(self.size(), &regions) {
+            return false;
+        }
+
+        for region in &mut regions {
+            region.sort_unstable();
+        }
+        regions.sort_unstable();
+
+        self.constraints
+            .retain(|constraint| !matches!(constraint, Constraint::Boxes | Constraint::Jigsaw(_)));
+        self.constraints.push(Constraint::Jigsaw(regions));
+        self.update_superpositions();
+
+        true
+    }
+}
+
+/// Whether `regions` are `size` connected regions of `size` squares that cover a board of the
+/// given size exactly once.
+pub(super) fn tiles(size: usize, regions: &[Vec<(usize, usize)>]) -> bool {
+    if regions.len() != size {
+        return false;
+    }
+
+    let mut covered = vec![vec![false; size]; size];
+    for region in regions {
+        if region.len() != size || !is_connected(region) {
+            return false;
+        }
+
+        for &(row, column) in region {
+            if row >= size || column >= size || std::mem::replace(&mut covered[row][column], true) {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Whether every square in the region can be reached from every other by steps up, down, left
+/// or right within it.
+fn is_connected(region: &[(usize, usize)]) -> bool {
+    let Some(&start) = region.first() else {
+        return true;
+    };
+
+    let mut reached = vec![start];
+    let mut queue = VecDeque::from([start]);
+    while let Some((row, column)) = queue.pop_front() {
+        for next in region.iter().copied().filter(|&(other_row, other_column)| {
+            row.abs_diff(other_row) + column.abs_diff(other_column) == 1
+        }) {
+            if !reached.contains(&next) {
+                reached.push(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    reached.len() == region.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Number;
+
+    use pretty_assertions::assert_eq;
+
+    fn regions() -> Vec<Vec<(usize, usize)>> {
+        vec![
+            vec![(3, 0), (3, 1), (3, 2), (2, 1)],
+            vec![(3, 3), (2, 2), (2, 3), (1, 3)],
+            vec![(2, 0), (1, 0), (1, 1), (0, 0)],
+            vec![(1, 2), (0, 1), (0, 2), (0, 3)],
+        ]
+    }
+
+    #[test]
+    fn regions_must_tile_the_board() {
+        let mut board = Board::new(2, 2).unwrap();
+
+        let mut overlapping = regions();
+        overlapping[0][3] = (2, 0);
+        assert!(!board.replace_boxes(overlapping));
+
+        let mut disconnected = regions();
+        disconnected[0].swap_remove(1);
+        disconnected[0].push((0, 0));
+        assert!(!board.replace_boxes(disconnected));
+
+        assert!(!board.replace_boxes(regions()[..3].to_vec()));
+        assert!(board.constraints().contains(&Constraint::Boxes));
+
+        assert!(board.replace_boxes(regions()));
+        assert!(!board.constraints().contains(&Constraint::Boxes));
+    }
+
+    #[test]
+    fn added_regions_replace_boxes() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(!board.add_constraint(Constraint::Jigsaw(regions()[..3].to_vec())));
+        assert!(board.constraints().contains(&Constraint::Boxes));
+
+        assert!(board.add_constraint(Constraint::Jigsaw(regions())));
+        let mut replaced = Board::new(2, 2).unwrap();
+        assert!(replaced.replace_boxes(regions()));
+        assert_eq!(replaced, board);
+    }
+
+    #[test]
+    fn regions_replace_boxes_when_solving() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.replace_boxes(regions()));
+
+        assert!(board.try_collapse(Number::One, (3, 2)));
+        assert!(!board.candidates((2, 1)).contains(&Number::One));
+        assert!(board.candidates((2, 3)).contains(&Number::One));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+        for region in regions() {
+            let mut numbers: Vec<_> = region
+                .iter()
+                .filter_map(|&location| solution.number(location))
+                .collect();
+            numbers.sort();
+            assert_eq!(Number::ALL[..4].to_vec(), numbers);
+        }
+    }
+}
@@ -0,0 +1,420 @@
+use super::{options, unsupported};
+use crate::{Board, Constraint, Number};
+
+/// Squares that must hold different numbers adding up to a sum are only narrowed down when the
+/// number of sets of numbers with that sum, times the `2^count` ways of sharing each set out among
+/// `count` squares, stays below this, since both grow too quickly on large boards.
+const MAX_DISTINCT_WORK: usize = 1 << 18;
+
+/// Outies may repeat numbers, so they are only narrowed down when there are this few of them.
+const MAX_OUTIE_CELLS: usize = 4;
+
+/// Removes every candidate in a cage that does not appear in any way of filling the cage with
+/// different numbers adding up to `sum`.
+pub(super) fn cage_eliminations(
+    board: &Board,
+    sum: usize,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    distinct_sum_eliminations(board, locations, sum)
+}
+
+/// Uses the rule that a house (a group of `size` squares holding every number once) adds up to
+/// `1 + 2 + ... + size`. Subtracting the cages that lie inside the house gives the sum of the
+/// remaining squares in it (the innies), and subtracting those from the cages sticking out of the
+/// house gives the sum of the squares they cover outside it (the outies).
+pub(super) fn house_eliminations(
+    board: &Board,
+    house: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let size = board.size();
+    if house.len() != size {
+        return Vec::new();
+    }
+
+    let mut inside_sum = 0;
+    let mut covered = Vec::new();
+    let mut sticking_out = Vec::new();
+    for (sum, locations) in cages(board) {
+        let inside = locations
+            .iter()
+            .filter(|location| house.contains(location))
+            .count();
+
+        if inside == locations.len() {
+            inside_sum += sum;
+            covered.extend_from_slice(locations);
+        } else if inside > 0 {
+            sticking_out.push((sum, locations));
+        }
+    }
+
+    let innies: Vec<_> = house
+        .iter()
+        .copied()
+        .filter(|location| !covered.contains(location))
+        .collect();
+    let Some(innies_sum) = (size * (size + 1) / 2).checked_sub(inside_sum) else {
+        return Vec::new();
+    };
+    if innies.is_empty() || (covered.is_empty() && sticking_out.is_empty()) {
+        return Vec::new();
+    }
+
+    let mut eliminations = Vec::new();
+    if !covered.is_empty() {
+        eliminations.extend(distinct_sum_eliminations(board, &innies, innies_sum));
+    }
+
+    let all_innies_stick_out = innies.iter().all(|location| {
+        sticking_out
+            .iter()
+            .any(|(_sum, locations)| locations.contains(location))
+    });
+    if !sticking_out.is_empty() && all_innies_stick_out {
+        let outies: Vec<_> = sticking_out
+            .iter()
+            .flat_map(|(_sum, locations)| locations.iter().copied())
+            .filter(|location| !house.contains(location))
+            .collect();
+        let sticking_out_sum: usize = sticking_out.iter().map(|(sum, _locations)| sum).sum();
+
+        if let Some(outies_sum) = sticking_out_sum.checked_sub(innies_sum) {
+            if outies.len() <= MAX_OUTIE_CELLS {
+                eliminations.extend(sum_eliminations(board, &outies, outies_sum));
+            }
+        }
+    }
+
+    eliminations
+}
+
+/// Whether the collapsed numbers in a cage already add up past `sum`, or add up to something else
+/// once the cage is full.
+pub(super) fn is_sum_broken(board: &Board, sum: usize, locations: &[(usize, usize)]) -> bool {
+    let numbers: Vec<_> = locations
+        .iter()
+        .filter_map(|&location| board.number(location))
+        .collect();
+    let total: usize = numbers.iter().map(|number| number.value()).sum();
+
+    total > sum || (numbers.len() == locations.len() && total != sum)
+}
+
+/// Every cage on the board as its sum and locations.
+fn cages(board: &Board) -> impl Iterator<Item = (usize, &[(usize, usize)])> {
+    board
+        .constraints()
+        .iter()
+        .filter_map(|constraint| match constraint {
+            Constraint::Cage { sum, locations } => Some((*sum, locations.as_slice())),
+            _ => None,
+        })
+}
+
+/// Every set of `count` different numbers out of the first `size` that add up to `sum`, as
+/// ascending indices, or `None` if there are more than `limit` of them.
+fn combinations(size: usize, count: usize, sum: usize, limit: usize) -> Option<Vec<Vec<usize>>> {
+    fn extend(
+        size: usize,
+        count: usize,
+        remaining: usize,
+        limit: usize,
+        partial: &mut Vec<usize>,
+        combinations: &mut Vec<Vec<usize>>,
+    ) -> bool {
+        // The numbers left to choose add up to an unbroken range of sums, so checking its ends
+        // means that every step taken leads to at least one combination.
+        let start = partial.last().map_or(0, |last| last + 1);
+        let left = count - partial.len();
+        if start + left > size
+            || (start + 1..=start + left).sum::<usize>() > remaining
+            || (size - left + 1..=size).sum::<usize>() < remaining
+        {
+            return true;
+        }
+
+        if left == 0 {
+            combinations.push(partial.clone());
+            return combinations.len() <= limit;
+        }
+
+        for index in start..size {
+            partial.push(index);
+            let within_limit = match remaining.checked_sub(index + 1) {
+                Some(remaining) => extend(size, count, remaining, limit, partial, combinations),
+                None => true,
+            };
+            partial.pop();
+            if !within_limit {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    let mut combinations = Vec::new();
+    extend(size, count, sum, limit, &mut Vec::new(), &mut combinations).then_some(combinations)
+}
+
+/// Removes every candidate that cannot take part in filling `locations` with different numbers
+/// adding up to `sum`. Each set of numbers with the right sum is checked for a way of giving each
+/// square its own number, working forwards and backwards through the squares so that every
+/// candidate is checked in one pass.
+fn distinct_sum_eliminations(
+    board: &Board,
+    locations: &[(usize, usize)],
+    sum: usize,
+) -> Vec<((usize, usize), Number)> {
+    let count = locations.len();
+    let limit = MAX_DISTINCT_WORK.checked_shr(count as u32).unwrap_or(0);
+    if count == 0 {
+        return Vec::new();
+    }
+    let Some(combinations) = combinations(board.size(), count, sum, limit) else {
+        return Vec::new();
+    };
+
+    let options: Vec<_> = locations
+        .iter()
+        .map(|&location| options(board, location))
+        .collect();
+    let mut supported = vec![vec![false; board.size()]; count];
+
+    for combination in combinations {
+        // Which members of the combination each square could hold, as bits.
+        let local_options: Vec<usize> = options
+            .iter()
+            .map(|numbers| {
+                combination
+                    .iter()
+                    .enumerate()
+                    .filter(|(_bit, index)| numbers.iter().any(|number| number.index() == **index))
+                    .fold(0, |mask, (bit, _index)| mask | 1 << bit)
+            })
+            .collect();
+        if local_options.contains(&0) {
+            continue;
+        }
+
+        let full = (1 << count) - 1;
+        let mut forward = vec![vec![false; 1 << count]; count + 1];
+        forward[0][0] = true;
+        for (i, &square_options) in local_options.iter().enumerate() {
+            for mask in 0..=full {
+                if !forward[i][mask] {
+                    continue;
+                }
+                for bit in 0..count {
+                    if square_options & 1 << bit != 0 && mask & 1 << bit == 0 {
+                        forward[i + 1][mask | 1 << bit] = true;
+                    }
+                }
+            }
+        }
+        if !forward[count][full] {
+            continue;
+        }
+
+        let mut backward = vec![vec![false; 1 << count]; count + 1];
+        backward[count][0] = true;
+        for (i, &square_options) in local_options.iter().enumerate().rev() {
+            for mask in 0..=full {
+                if !backward[i + 1][mask] {
+                    continue;
+                }
+                for bit in 0..count {
+                    if square_options & 1 << bit != 0 && mask & 1 << bit == 0 {
+                        backward[i][mask | 1 << bit] = true;
+                    }
+                }
+            }
+        }
+
+        for (i, &square_options) in local_options.iter().enumerate() {
+            for bit in (0..count).filter(|bit| square_options & 1 << bit != 0) {
+                let fits = (0..=full).any(|before| {
+                    forward[i][before]
+                        && before & 1 << bit == 0
+                        && backward[i + 1][full ^ before ^ 1 << bit]
+                });
+                if fits {
+                    supported[i][combination[bit]] = true;
+                }
+            }
+        }
+    }
+
+    unsupported(board, locations, &supported)
+}
+
+/// Removes every candidate that cannot take part in filling `locations` with numbers adding up to
+/// `sum`, where only squares that neighbor each other need different numbers.
+fn sum_eliminations(
+    board: &Board,
+    locations: &[(usize, usize)],
+    sum: usize,
+) -> Vec<((usize, usize), Number)> {
+    fn search(
+        board: &Board,
+        locations: &[(usize, usize)],
+        options: &[Vec<Number>],
+        remaining: usize,
+        chosen: &mut Vec<Number>,
+        supported: &mut [Vec<bool>],
+    ) {
+        let i = chosen.len();
+        if i == locations.len() {
+            if remaining == 0 {
+                for (square, number) in chosen.iter().enumerate() {
+                    supported[square][number.index()] = true;
+                }
+            }
+            return;
+        }
+
+        let neighbors = board.find_neighbor_locations(locations[i]);
+        for &number in &options[i] {
+            let clashes = chosen
+                .iter()
+                .zip(locations)
+                .any(|(other, location)| *other == number && neighbors.contains(location));
+            if number.value() > remaining || clashes {
+                continue;
+            }
+
+            chosen.push(number);
+            search(
+                board,
+                locations,
+                options,
+                remaining - number.value(),
+                chosen,
+                supported,
+            );
+            chosen.pop();
+        }
+    }
+
+    let options: Vec<_> = locations
+        .iter()
+        .map(|&location| options(board, location))
+        .collect();
+    let mut supported = vec![vec![false; board.size()]; locations.len()];
+    search(
+        board,
+        locations,
+        &options,
+        sum,
+        &mut Vec::with_capacity(locations.len()),
+        &mut supported,
+    );
+
+    unsupported(board, locations, &supported)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cage_sums_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 17,
+            locations: vec![(0, 0), (0, 1)],
+        }));
+        assert_eq!(vec![Number::Eight, Number::Nine], board.candidates((0, 0)));
+
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 6,
+            locations: vec![(1, 0), (1, 1), (2, 0)],
+        }));
+        assert_eq!(
+            vec![Number::One, Number::Two, Number::Three],
+            board.candidates((2, 0))
+        );
+
+        assert!(board.try_collapse(Number::One, (1, 0)));
+        assert!(board.try_collapse(Number::Two, (1, 1)));
+        assert_eq!(vec![Number::Three], board.candidates((2, 0)));
+    }
+
+    #[test]
+    fn innies_and_outies_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 40,
+            locations: (0..8).map(|column| (0, column)).collect(),
+        }));
+        assert_eq!(vec![Number::Five], board.candidates((0, 8)));
+
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 12,
+            locations: vec![(0, 8), (1, 8)],
+        }));
+        assert_eq!(vec![Number::Seven], board.candidates((1, 8)));
+    }
+
+    #[test]
+    fn cages_on_large_boards_are_added_quickly() {
+        for (box_size, middle_sum) in [(4, 76), (5, 117)] {
+            let row: Vec<_> = (0..9).map(|column| (0, column)).collect();
+
+            let mut board = Board::new(box_size, box_size).unwrap();
+            assert!(board.add_constraint(Constraint::Cage {
+                sum: 45,
+                locations: row.clone(),
+            }));
+            assert_eq!(Number::ALL[..9], board.candidates((0, 0)));
+            assert_eq!(
+                Number::ALL[9..box_size * box_size],
+                board.candidates((0, 9))
+            );
+
+            let mut board = Board::new(box_size, box_size).unwrap();
+            assert!(board.add_constraint(Constraint::Cage {
+                sum: middle_sum,
+                locations: row,
+            }));
+            assert!(board.try_collapse(Number::One, (1, 0)));
+        }
+    }
+
+    #[test]
+    fn killer_puzzles_solve() {
+        let mut board = Board::new(2, 2).unwrap();
+        // Each box is split into two cages, across in the boxes on the a1 diagonal and up and
+        // down in the others, like a pinwheel.
+        for (sum, locations) in [
+            (3, [(0, 0), (0, 1)]),
+            (7, [(1, 0), (1, 1)]),
+            (4, [(0, 2), (1, 2)]),
+            (6, [(0, 3), (1, 3)]),
+            (6, [(2, 0), (3, 0)]),
+            (4, [(2, 1), (3, 1)]),
+            (7, [(2, 2), (2, 3)]),
+            (3, [(3, 2), (3, 3)]),
+        ] {
+            assert!(board.add_constraint(Constraint::Cage {
+                sum,
+                locations: locations.to_vec(),
+            }));
+        }
+        assert!(!board.clone().try_collapse(Number::Three, (0, 0)));
+        assert_eq!(1, board.count_solutions(2));
+
+        let solved = board.solve().unwrap();
+        for (row, values) in [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]]
+            .iter()
+            .enumerate()
+        {
+            for (column, &value) in values.iter().enumerate() {
+                assert_eq!(Some(Number::ALL[value - 1]), solved.number((row, column)));
+            }
+        }
+    }
+}
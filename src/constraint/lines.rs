@@ -0,0 +1,261 @@
+use super::options;
+use crate::{Board, Number};
+
+/// Keeps every square on a thermometer between the lowest number the squares before it allow
+/// and the highest number the squares after it allow, since the numbers strictly increase from
+/// the bulb.
+pub(super) fn thermometer_eliminations(
+    board: &Board,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let options: Vec<_> = locations
+        .iter()
+        .map(|&location| options(board, location))
+        .collect();
+
+    let mut lowest = vec![0; locations.len()];
+    let mut previous = 0;
+    for (i, numbers) in options.iter().enumerate() {
+        previous = numbers
+            .iter()
+            .map(|number| number.value())
+            .find(|&value| value > previous)
+            .unwrap_or(board.size() + 1);
+        lowest[i] = previous;
+    }
+
+    let mut highest = vec![0; locations.len()];
+    let mut next = usize::MAX;
+    for (i, numbers) in options.iter().enumerate().rev() {
+        next = numbers
+            .iter()
+            .rev()
+            .map(|number| number.value())
+            .find(|&value| value < next)
+            .unwrap_or(0);
+        highest[i] = next;
+    }
+
+    outside(board, locations, |i, value| {
+        value < lowest[i] || value > highest[i]
+    })
+}
+
+/// Keeps the circle of an arrow within the smallest and largest sums its line could add up to,
+/// and each square on the line small enough that the rest of the line can still fit under the
+/// circle.
+pub(super) fn arrow_eliminations(
+    board: &Board,
+    circle: (usize, usize),
+    line: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let (Some((lowest_sum, highest_sum)), Some((lowest_circle, highest_circle))) =
+        (sum_bounds(board, line), bounds(board, circle))
+    else {
+        return Vec::new();
+    };
+
+    let mut eliminations: Vec<_> = board
+        .candidates(circle)
+        .into_iter()
+        .filter(|number| number.value() < lowest_sum || number.value() > highest_sum)
+        .map(|number| (circle, number))
+        .collect();
+    eliminations.extend(bounded_sum_eliminations(
+        board,
+        line,
+        lowest_circle,
+        highest_circle,
+    ));
+
+    eliminations
+}
+
+/// Removes every candidate on `line` that would take its sum outside `lowest..=highest` whatever
+/// the rest of the line holds. Numbers on the line are allowed to repeat.
+pub(super) fn bounded_sum_eliminations(
+    board: &Board,
+    line: &[(usize, usize)],
+    lowest: usize,
+    highest: usize,
+) -> Vec<((usize, usize), Number)> {
+    let Some(line_bounds) = line
+        .iter()
+        .map(|&location| bounds(board, location))
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Vec::new();
+    };
+    let lowest_sum: usize = line_bounds.iter().map(|(low, _high)| low).sum();
+    let highest_sum: usize = line_bounds.iter().map(|(_low, high)| high).sum();
+
+    outside(board, line, |i, value| {
+        let (low, high) = line_bounds[i];
+
+        value + lowest_sum - low > highest || value + highest_sum - high < lowest
+    })
+}
+
+/// Keeps each pair of squares that mirror each other on a palindrome to the numbers they could
+/// both hold.
+pub(super) fn palindrome_eliminations(
+    board: &Board,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let options: Vec<_> = locations
+        .iter()
+        .map(|&location| options(board, location))
+        .collect();
+
+    outside(board, locations, |i, value| {
+        !options[locations.len() - 1 - i]
+            .iter()
+            .any(|number| number.value() == value)
+    })
+}
+
+/// Whether the collapsed numbers on a thermometer fail to increase quickly enough, since each
+/// step along it must go up by at least one.
+pub(super) fn is_thermometer_broken(board: &Board, locations: &[(usize, usize)]) -> bool {
+    let values: Vec<_> = locations
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &location)| board.number(location).map(|number| (i, number.value())))
+        .collect();
+
+    values
+        .windows(2)
+        .any(|pair| pair[1].1 < pair[0].1 + (pair[1].0 - pair[0].0))
+}
+
+/// Whether an arrow's line already adds up past its circle, or adds up to something else once
+/// the whole arrow is filled in.
+pub(super) fn is_arrow_broken(
+    board: &Board,
+    circle: (usize, usize),
+    line: &[(usize, usize)],
+) -> bool {
+    let Some(circle) = board.number(circle) else {
+        return false;
+    };
+    let numbers: Vec<_> = line
+        .iter()
+        .filter_map(|&location| board.number(location))
+        .collect();
+    let total: usize = numbers.iter().map(|number| number.value()).sum();
+
+    total > circle.value() || (numbers.len() == line.len() && total != circle.value())
+}
+
+/// Whether two mirrored squares on a palindrome hold different numbers.
+pub(super) fn is_palindrome_broken(board: &Board, locations: &[(usize, usize)]) -> bool {
+    locations
+        .iter()
+        .zip(locations.iter().rev())
+        .any(
+            |(&first, &second)| match (board.number(first), board.number(second)) {
+                (Some(first), Some(second)) => first != second,
+                _ => false,
+            },
+        )
+}
+
+/// The lowest and highest values that could be at `location`, or None if nothing could be.
+fn bounds(board: &Board, location: (usize, usize)) -> Option<(usize, usize)> {
+    let options = options(board, location);
+
+    Some((options.first()?.value(), options.last()?.value()))
+}
+
+/// The smallest and largest sums the numbers on `line` could add up to, or None if some square
+/// on it has no options left.
+fn sum_bounds(board: &Board, line: &[(usize, usize)]) -> Option<(usize, usize)> {
+    line.iter()
+        .map(|&location| bounds(board, location))
+        .try_fold((0, 0), |(lowest, highest), bound| {
+            let (low, high) = bound?;
+            Some((lowest + low, highest + high))
+        })
+}
+
+/// Every candidate of the unsolved squares in `locations` for which `is_outside(i, value)` holds,
+/// where `i` is the square's position along the line.
+fn outside(
+    board: &Board,
+    locations: &[(usize, usize)],
+    is_outside: impl Fn(usize, usize) -> bool,
+) -> Vec<((usize, usize), Number)> {
+    locations
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &location)| {
+            board
+                .candidates(location)
+                .into_iter()
+                .filter(|number| is_outside(i, number.value()))
+                .map(move |number| (location, number))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn thermometers_increase_from_the_bulb() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Thermometer(vec![(0, 0), (1, 1), (2, 1)])));
+        assert_eq!(Number::ALL[..7].to_vec(), board.candidates((0, 0)));
+        assert_eq!(Number::ALL[2..9].to_vec(), board.candidates((2, 1)));
+
+        assert!(board.try_collapse(Number::Four, (2, 1)));
+        assert_eq!(vec![Number::One, Number::Two], board.candidates((0, 0)));
+        assert_eq!(vec![Number::Two, Number::Three], board.candidates((1, 1)));
+        assert!(!board.clone().try_collapse(Number::Three, (0, 0)));
+    }
+
+    #[test]
+    fn arrows_add_up_to_their_circle() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Arrow {
+            circle: (4, 4),
+            line: vec![(4, 5), (5, 6), (6, 6)],
+        }));
+        assert_eq!(Number::ALL[2..9].to_vec(), board.candidates((4, 4)));
+
+        assert!(board.try_collapse(Number::Five, (4, 4)));
+        assert_eq!(Number::ALL[..3].to_vec(), board.candidates((4, 5)));
+
+        assert!(board.try_collapse(Number::One, (4, 5)));
+        assert!(board.try_collapse(Number::One, (5, 6)));
+        assert_eq!(vec![Number::Three], board.candidates((6, 6)));
+    }
+
+    #[test]
+    fn palindromes_mirror_themselves() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Palindrome(vec![(0, 0), (1, 3), (2, 6), (3, 8)])));
+
+        assert!(board.try_collapse(Number::Seven, (0, 0)));
+        assert_eq!(vec![Number::Seven], board.candidates((3, 8)));
+        assert!(!board.clone().try_collapse(Number::Six, (3, 8)));
+    }
+
+    #[test]
+    fn line_puzzles_solve() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::Thermometer(vec![(0, 0), (1, 0), (2, 0)])));
+        assert!(board.add_constraint(Constraint::Arrow {
+            circle: (3, 3),
+            line: vec![(2, 2), (1, 2)],
+        }));
+        assert!(board.add_constraint(Constraint::Palindrome(vec![(0, 1), (1, 3), (2, 2)])));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+    }
+}
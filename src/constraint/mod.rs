@@ -0,0 +1,665 @@
+mod chess;
+mod dots;
+mod jigsaw;
+mod killer;
+mod lines;
+mod outside;
+mod parity;
+
+pub use dots::Dot;
+
+use crate::{Board, Number};
+
+/// A rule restricting which numbers may be placed where on a [`Board`]. Every board starts with
+/// [`Constraint::Rows`], [`Constraint::Columns`] and [`Constraint::Boxes`], and variants add
+/// more.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Constraint {
+    /// Every row holds each number once.
+    Rows,
+    /// Every column holds each number once.
+    Columns,
+    /// Every box holds each number once.
+    Boxes,
+    /// Every one of these irregular regions holds each number once, taking the place of the boxes
+    /// in Jigsaw Sudoku. Adding it through [`Board::replace_boxes`] or [`Board::add_constraint`]
+    /// removes [`Constraint::Boxes`].
+    Jigsaw(Vec<Vec<(usize, usize)>>),
+    /// Every location in the region holds a different number.
+    Region(Vec<(usize, usize)>),
+    /// Every location on the diagonal holds a different number, as in X-Sudoku.
+    Diagonal(Vec<(usize, usize)>),
+    /// Every location in the window holds a different number, as in Windoku.
+    Window(Vec<(usize, usize)>),
+    /// No two squares a knight's move apart hold the same number.
+    AntiKnight,
+    /// No two squares a king's move apart (including diagonally) hold the same number.
+    AntiKing,
+    /// The locations hold different numbers adding up to `sum`, as in Killer Sudoku.
+    Cage {
+        sum: usize,
+        locations: Vec<(usize, usize)>,
+    },
+    /// The numbers strictly increase along the locations, starting from the bulb.
+    Thermometer(Vec<(usize, usize)>),
+    /// The numbers along the line add up to the number in the circle. Numbers on the line may
+    /// repeat unless another constraint forbids it.
+    Arrow {
+        circle: (usize, usize),
+        line: Vec<(usize, usize)>,
+    },
+    /// The numbers read the same forwards and backwards along the locations.
+    Palindrome(Vec<(usize, usize)>),
+    /// The numbers in two side-by-side locations agree with the dot between them.
+    Dot {
+        dot: Dot,
+        locations: [(usize, usize); 2],
+    },
+    /// Every side-by-side pair whose numbers agree with this kind of dot is given one, so pairs
+    /// without a dot of its family (white and black Kropki dots, or X and V) must not agree with
+    /// it.
+    AllGiven(Dot),
+    /// The numbers between the lowest and highest number in a row or column add up to `sum`.
+    /// The locations run across the board starting next to the clue.
+    Sandwich {
+        sum: usize,
+        locations: Vec<(usize, usize)>,
+    },
+    /// The numbers along a diagonal add up to `sum`, and may repeat unless another constraint
+    /// forbids it. The locations run across the board starting next to the clue.
+    LittleKiller {
+        sum: usize,
+        locations: Vec<(usize, usize)>,
+    },
+    /// Reading the numbers in a row or column as skyscrapers of that height, `count` of them can
+    /// be seen from the clue, since each hides every shorter one behind it. The locations run
+    /// across the board starting next to the clue.
+    Skyscraper {
+        count: usize,
+        locations: Vec<(usize, usize)>,
+    },
+    /// The number at `greater` is larger than the number at `lesser`, which is next to it.
+    GreaterThan {
+        greater: (usize, usize),
+        lesser: (usize, usize),
+    },
+    /// The locations are shaded to show that they hold even numbers.
+    Even(Vec<(usize, usize)>),
+    /// The locations are shaded to show that they hold odd numbers.
+    Odd(Vec<(usize, usize)>),
+}
+impl Constraint {
+    /// The two diagonals running corner to corner on a board of the given size, which X-Sudoku
+    /// adds to the usual rows, columns and boxes.
+    pub fn diagonals(size: usize) -> [Self; 2] {
+        [
+            Self::Diagonal((0..size).map(|i| (i, i)).collect()),
+            Self::Diagonal((0..size).map(|row| (row, size - 1 - row)).collect()),
+        ]
+    }
+
+    /// The extra box-sized regions of Windoku, which sit one square in from the edge of the board
+    /// with one line between each of them. A 9x9 board has four, covering rows and columns 2–4
+    /// and 6–8.
+    pub fn windows(box_height: usize, box_width: usize) -> Vec<Self> {
+        let size = box_height * box_width;
+        let starts = |length: usize| {
+            (1..)
+                .step_by(length + 1)
+                .take_while(move |start| start + length < size)
+        };
+
+        starts(box_height)
+            .flat_map(|row| {
+                starts(box_width).map(move |column| {
+                    Self::Window(
+                        (row..row + box_height)
+                            .flat_map(|row| {
+                                (column..column + box_width).map(move |column| (row, column))
+                            })
+                            .collect(),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Removals of candidates that this constraint forces beyond its neighbors, given the
+    /// numbers and candidates currently on `board`.
+    pub fn eliminations(&self, board: &Board) -> Vec<((usize, usize), Number)> {
+        match self {
+            Self::Rows
+            | Self::Columns
+            | Self::Boxes
+            | Self::Jigsaw(_)
+            | Self::Region(_)
+            | Self::Diagonal(_)
+            | Self::Window(_) => {
+                let has_cages = board
+                    .constraints()
+                    .iter()
+                    .any(|constraint| matches!(constraint, Self::Cage { .. }));
+                if !has_cages {
+                    return Vec::new();
+                }
+
+                self.regions(board)
+                    .iter()
+                    .flat_map(|house| killer::house_eliminations(board, house))
+                    .collect()
+            }
+            Self::AntiKnight | Self::AntiKing => Vec::new(),
+            Self::Cage { sum, locations } => killer::cage_eliminations(board, *sum, locations),
+            Self::Thermometer(locations) => lines::thermometer_eliminations(board, locations),
+            Self::Arrow { circle, line } => lines::arrow_eliminations(board, *circle, line),
+            Self::Palindrome(locations) => lines::palindrome_eliminations(board, locations),
+            Self::Dot { dot, locations } => dots::dot_eliminations(board, *dot, *locations),
+            Self::AllGiven(dot) => dots::all_given_eliminations(board, *dot),
+            Self::Sandwich { sum, locations } => {
+                outside::sandwich_eliminations(board, *sum, locations)
+            }
+            Self::LittleKiller { sum, locations } => {
+                outside::little_killer_eliminations(board, *sum, locations)
+            }
+            Self::Skyscraper { count, locations } => {
+                outside::skyscraper_eliminations(board, *count, locations)
+            }
+            Self::GreaterThan { greater, lesser } => {
+                dots::comparison_eliminations(board, *greater, *lesser)
+            }
+            Self::Even(locations) => parity::parity_eliminations(board, locations, true),
+            Self::Odd(locations) => parity::parity_eliminations(board, locations, false),
+        }
+    }
+
+    /// Whether the collapsed numbers on `board` break this constraint.
+    pub fn is_violated(&self, board: &Board) -> bool {
+        let repeats = self.regions(board).iter().any(|region| {
+            let mut seen = vec![false; board.size()];
+
+            region
+                .iter()
+                .filter_map(|&location| board.number(location))
+                .any(|number| std::mem::replace(&mut seen[number.index()], true))
+        });
+
+        repeats
+            || match self {
+                Self::Rows
+                | Self::Columns
+                | Self::Boxes
+                | Self::Jigsaw(_)
+                | Self::Region(_)
+                | Self::Diagonal(_)
+                | Self::Window(_) => false,
+                Self::AntiKnight | Self::AntiKing => (0..board.size())
+                    .flat_map(|row| (0..board.size()).map(move |column| (row, column)))
+                    .any(|location| {
+                        board.number(location).is_some_and(|number| {
+                            self.neighbors(board, location)
+                                .into_iter()
+                                .any(|neighbor| board.number(neighbor) == Some(number))
+                        })
+                    }),
+                Self::Cage { sum, locations } => killer::is_sum_broken(board, *sum, locations),
+                Self::Thermometer(locations) => lines::is_thermometer_broken(board, locations),
+                Self::Arrow { circle, line } => lines::is_arrow_broken(board, *circle, line),
+                Self::Palindrome(locations) => lines::is_palindrome_broken(board, locations),
+                Self::Dot { dot, locations } => dots::is_dot_broken(board, *dot, *locations),
+                Self::AllGiven(dot) => dots::is_all_given_broken(board, *dot),
+                Self::Sandwich { sum, locations } => {
+                    outside::is_sandwich_broken(board, *sum, locations)
+                }
+                Self::LittleKiller { sum, locations } => {
+                    killer::is_sum_broken(board, *sum, locations)
+                }
+                Self::Skyscraper { count, locations } => {
+                    outside::is_skyscraper_broken(board, *count, locations)
+                }
+                Self::GreaterThan { greater, lesser } => {
+                    dots::is_comparison_broken(board, *greater, *lesser)
+                }
+                Self::Even(locations) => parity::is_parity_broken(board, locations, true),
+                Self::Odd(locations) => parity::is_parity_broken(board, locations, false),
+            }
+    }
+
+    /// The locations that may not hold the same number as `location` under this constraint.
+    pub fn neighbors(&self, board: &Board, location: (usize, usize)) -> Vec<(usize, usize)> {
+        match self {
+            Self::Rows => (0..board.size())
+                .filter(|&column| column != location.1)
+                .map(|column| (location.0, column))
+                .collect(),
+            Self::Columns => (0..board.size())
+                .filter(|&row| row != location.0)
+                .map(|row| (row, location.1))
+                .collect(),
+            Self::Boxes => {
+                let corner = (
+                    location.0 / board.box_height() * board.box_height(),
+                    location.1 / board.box_width() * board.box_width(),
+                );
+
+                (0..board.box_height())
+                    .flat_map(|i| (0..board.box_width()).map(move |j| (corner.0 + i, corner.1 + j)))
+                    .filter(|&neighbor| neighbor != location)
+                    .collect()
+            }
+            Self::Jigsaw(regions) => regions
+                .iter()
+                .filter(|region| region.contains(&location))
+                .flat_map(|region| region.iter().copied())
+                .filter(|&neighbor| neighbor != location)
+                .collect(),
+            Self::AntiKnight => chess::reachable(board.size(), location, &chess::KNIGHT_MOVES),
+            Self::AntiKing => chess::reachable(board.size(), location, &chess::KING_MOVES),
+            Self::Region(locations)
+            | Self::Diagonal(locations)
+            | Self::Window(locations)
+            | Self::Cage { locations, .. }
+            | Self::Thermometer(locations) => {
+                if locations.contains(&location) {
+                    locations
+                        .iter()
+                        .copied()
+                        .filter(|&neighbor| neighbor != location)
+                        .collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::Arrow { .. }
+            | Self::Palindrome(_)
+            | Self::Dot { .. }
+            | Self::AllGiven(_)
+            | Self::Sandwich { .. }
+            | Self::LittleKiller { .. }
+            | Self::Skyscraper { .. }
+            | Self::GreaterThan { .. }
+            | Self::Even(_)
+            | Self::Odd(_) => Vec::new(),
+        }
+    }
+
+    /// The groups of locations that must each hold different numbers under this constraint.
+    pub fn regions(&self, board: &Board) -> Vec<Vec<(usize, usize)>> {
+        let size = board.size();
+
+        match self {
+            Self::Rows => (0..size)
+                .map(|row| (0..size).map(|column| (row, column)).collect())
+                .collect(),
+            Self::Columns => (0..size)
+                .map(|column| (0..size).map(|row| (row, column)).collect())
+                .collect(),
+            Self::Boxes => (0..size)
+                .map(|box_index| {
+                    let corner = (
+                        box_index / (size / board.box_width()) * board.box_height(),
+                        box_index % (size / board.box_width()) * board.box_width(),
+                    );
+
+                    (0..board.box_height())
+                        .flat_map(|i| {
+                            (0..board.box_width()).map(move |j| (corner.0 + i, corner.1 + j))
+                        })
+                        .collect()
+                })
+                .collect(),
+            Self::Jigsaw(regions) => regions.clone(),
+            Self::AntiKnight | Self::AntiKing => Vec::new(),
+            Self::Region(locations)
+            | Self::Diagonal(locations)
+            | Self::Window(locations)
+            | Self::Cage { locations, .. }
+            | Self::Thermometer(locations) => {
+                vec![locations.clone()]
+            }
+            Self::Arrow { .. }
+            | Self::Palindrome(_)
+            | Self::Dot { .. }
+            | Self::AllGiven(_)
+            | Self::Sandwich { .. }
+            | Self::LittleKiller { .. }
+            | Self::Skyscraper { .. }
+            | Self::GreaterThan { .. }
+            | Self::Even(_)
+            | Self::Odd(_) => Vec::new(),
+        }
+    }
+
+    /// Whether this constraint makes sense on a board of the given size: every location it refers
+    /// to must be on the board, irregular boxes must tile it, dots and inequalities must sit
+    /// between side-by-side squares, and outside clues must look across the whole board.
+    pub(crate) fn fits(&self, size: usize) -> bool {
+        let on_board = self
+            .locations()
+            .iter()
+            .all(|location| location.0 < size && location.1 < size);
+
+        on_board
+            && match self {
+                Self::Jigsaw(regions) => jigsaw::tiles(size, regions),
+                Self::Dot { locations, .. } => dots::are_adjacent(locations[0], locations[1]),
+                Self::GreaterThan { greater, lesser } => dots::are_adjacent(*greater, *lesser),
+                Self::Sandwich { locations, .. } | Self::Skyscraper { locations, .. } => {
+                    outside::crosses_board(size, locations, false)
+                }
+                Self::LittleKiller { locations, .. } => {
+                    outside::crosses_board(size, locations, true)
+                }
+                _ => true,
+            }
+    }
+
+    /// Every location this constraint refers to explicitly.
+    pub(crate) fn locations(&self) -> Vec<(usize, usize)> {
+        match self {
+            Self::Rows
+            | Self::Columns
+            | Self::Boxes
+            | Self::AntiKnight
+            | Self::AntiKing
+            | Self::AllGiven(_) => Vec::new(),
+            Self::Jigsaw(regions) => regions.iter().flatten().copied().collect(),
+            Self::Region(locations)
+            | Self::Diagonal(locations)
+            | Self::Window(locations)
+            | Self::Cage { locations, .. }
+            | Self::Thermometer(locations)
+            | Self::Palindrome(locations)
+            | Self::Sandwich { locations, .. }
+            | Self::LittleKiller { locations, .. }
+            | Self::Skyscraper { locations, .. }
+            | Self::Even(locations)
+            | Self::Odd(locations) => locations.clone(),
+            Self::Arrow { circle, line } => std::iter::once(*circle).chain(line.clone()).collect(),
+            Self::Dot { locations, .. } => locations.to_vec(),
+            Self::GreaterThan { greater, lesser } => vec![*greater, *lesser],
+        }
+    }
+
+    /// The character shown next to `location` when the board is displayed, if this constraint
+    /// marks it. Diagonals are drawn with slashes leaning the way they run on screen, where row 1
+    /// is at the bottom, and windows with pluses. Even squares are drawn with a hash and odd ones
+    /// with a circle. An inequality is drawn as `<` or `>` beside the left square of the pair, or
+    /// as `^` or `v` beside the upper one, pointing at the smaller number.
+    pub(crate) fn marker(&self, location: (usize, usize)) -> Option<char> {
+        match self {
+            Self::Diagonal(locations) => {
+                if !locations.contains(&location) {
+                    return None;
+                }
+
+                let rising = locations.iter().all(|other| {
+                    other.0 as isize - other.1 as isize == location.0 as isize - location.1 as isize
+                });
+                let falling = locations
+                    .iter()
+                    .all(|other| other.0 + other.1 == location.0 + location.1);
+
+                Some(match (rising, falling) {
+                    (true, _) => '/',
+                    (false, true) => '\\',
+                    (false, false) => '*',
+                })
+            }
+            Self::Window(locations) => locations.contains(&location).then_some('+'),
+            Self::Even(locations) => locations.contains(&location).then_some('#'),
+            Self::Odd(locations) => locations.contains(&location).then_some('o'),
+            Self::GreaterThan { greater, lesser } => {
+                if greater.0 == lesser.0 && location == *greater.min(lesser) {
+                    Some(if greater < lesser { '>' } else { '<' })
+                } else if greater.1 == lesser.1 && location == *greater.max(lesser) {
+                    Some(if greater > lesser { 'v' } else { '^' })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Where this constraint's clue is written outside the board, as a row and column just off
+    /// the edge, and what is written there, if it is an outside clue. Little killers are written
+    /// with a slash leaning the way their diagonal runs on screen.
+    pub(crate) fn outside_clue(&self) -> Option<((isize, isize), String)> {
+        let (text, locations) = match self {
+            Self::Sandwich { sum, locations } => (sum.to_string(), locations),
+            Self::Skyscraper { count, locations } => (count.to_string(), locations),
+            Self::LittleKiller { sum, locations } => (sum.to_string(), locations),
+            _ => return None,
+        };
+        let [first, second, ..] = locations.as_slice() else {
+            return None;
+        };
+        let step = (
+            second.0 as isize - first.0 as isize,
+            second.1 as isize - first.1 as isize,
+        );
+        let text = match self {
+            Self::LittleKiller { .. } if step.0 == step.1 => text + "/",
+            Self::LittleKiller { .. } => text + "\\",
+            _ => text,
+        };
+
+        Some(((first.0 as isize - step.0, first.1 as isize - step.1), text))
+    }
+
+    /// Whether this constraint cares about the values of numbers rather than just whether they
+    /// differ, so that relabeling the numbers would break it.
+    pub(crate) fn uses_values(&self) -> bool {
+        matches!(
+            self,
+            Self::Cage { .. }
+                | Self::Thermometer(_)
+                | Self::Arrow { .. }
+                | Self::Dot { .. }
+                | Self::AllGiven(_)
+                | Self::Sandwich { .. }
+                | Self::LittleKiller { .. }
+                | Self::Skyscraper { .. }
+                | Self::GreaterThan { .. }
+                | Self::Even(_)
+                | Self::Odd(_)
+        )
+    }
+
+    /// Whether this constraint cares about which squares are next to each other, either in its
+    /// rule or in the shape it is drawn as, which only rotations and reflections of the board
+    /// keep.
+    pub(crate) fn uses_adjacency(&self) -> bool {
+        matches!(
+            self,
+            Self::Jigsaw(_)
+                | Self::Region(_)
+                | Self::Diagonal(_)
+                | Self::Window(_)
+                | Self::Cage { .. }
+                | Self::Thermometer(_)
+                | Self::Arrow { .. }
+                | Self::Palindrome(_)
+                | Self::AntiKnight
+                | Self::AntiKing
+                | Self::Dot { .. }
+                | Self::AllGiven(_)
+                | Self::Sandwich { .. }
+                | Self::LittleKiller { .. }
+                | Self::Skyscraper { .. }
+                | Self::GreaterThan { .. }
+        )
+    }
+
+    /// Moves every location this constraint refers to, for when the board is rearranged. Rows,
+    /// columns and boxes are kept as they are, since every rearrangement of the board keeps them,
+    /// and so are chess moves and undotted pairs, since boards using them are only ever rotated or
+    /// reflected. Lines keep their order, since it matters which end is which.
+    pub(crate) fn map_locations(&mut self, map: impl Fn((usize, usize)) -> (usize, usize)) {
+        match self {
+            Self::Rows
+            | Self::Columns
+            | Self::Boxes
+            | Self::AntiKnight
+            | Self::AntiKing
+            | Self::AllGiven(_) => {}
+            Self::Jigsaw(regions) => {
+                for region in regions.iter_mut() {
+                    region
+                        .iter_mut()
+                        .for_each(|location| *location = map(*location));
+                    region.sort_unstable();
+                }
+                regions.sort_unstable();
+            }
+            Self::Region(locations)
+            | Self::Diagonal(locations)
+            | Self::Window(locations)
+            | Self::Cage { locations, .. }
+            | Self::Even(locations)
+            | Self::Odd(locations) => {
+                locations
+                    .iter_mut()
+                    .for_each(|location| *location = map(*location));
+                locations.sort_unstable();
+            }
+            Self::Thermometer(locations)
+            | Self::Palindrome(locations)
+            | Self::Sandwich { locations, .. }
+            | Self::LittleKiller { locations, .. }
+            | Self::Skyscraper { locations, .. } => locations
+                .iter_mut()
+                .for_each(|location| *location = map(*location)),
+            Self::Arrow { circle, line } => {
+                *circle = map(*circle);
+                line.iter_mut()
+                    .for_each(|location| *location = map(*location));
+            }
+            Self::Dot { locations, .. } => {
+                locations
+                    .iter_mut()
+                    .for_each(|location| *location = map(*location));
+                locations.sort_unstable();
+            }
+            Self::GreaterThan { greater, lesser } => {
+                *greater = map(*greater);
+                *lesser = map(*lesser);
+            }
+        }
+    }
+}
+
+/// The numbers that could be at `location`, counting a collapsed number as its only option.
+fn options(board: &Board, location: (usize, usize)) -> Vec<Number> {
+    match board.number(location) {
+        Some(number) => vec![number],
+        None => board.candidates(location),
+    }
+}
+
+/// Every candidate of the unsolved squares in `locations` that is not marked as supported.
+fn unsupported(
+    board: &Board,
+    locations: &[(usize, usize)],
+    supported: &[Vec<bool>],
+) -> Vec<((usize, usize), Number)> {
+    locations
+        .iter()
+        .zip(supported)
+        .flat_map(|(&location, supported)| {
+            board
+                .candidates(location)
+                .into_iter()
+                .filter(|number| !supported[number.index()])
+                .map(move |number| (location, number))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn diagonals_propagate_and_solve() {
+        let mut board = Board::default();
+        for diagonal in Constraint::diagonals(9) {
+            assert!(board.add_constraint(diagonal));
+        }
+
+        assert!(board.try_collapse(Number::Five, (4, 4)));
+        assert!(!board.candidates((0, 0)).contains(&Number::Five));
+        assert!(!board.candidates((8, 0)).contains(&Number::Five));
+        assert!(board.candidates((7, 0)).contains(&Number::Five));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+        for diagonal in Constraint::diagonals(9) {
+            let mut numbers: Vec<_> = diagonal.regions(&solution)[0]
+                .iter()
+                .filter_map(|&location| solution.number(location))
+                .collect();
+            numbers.sort();
+            assert_eq!(Number::ALL[..9].to_vec(), numbers);
+        }
+    }
+
+    #[test]
+    fn windows_sit_between_boxes() {
+        let windows = Constraint::windows(3, 3);
+        assert_eq!(4, windows.len());
+        assert_eq!(
+            Constraint::Window(vec![
+                (5, 1),
+                (5, 2),
+                (5, 3),
+                (6, 1),
+                (6, 2),
+                (6, 3),
+                (7, 1),
+                (7, 2),
+                (7, 3),
+            ]),
+            windows[2]
+        );
+
+        let mut board = Board::default();
+        for window in windows {
+            assert!(board.add_constraint(window));
+        }
+        assert!(board.try_collapse(Number::Nine, (1, 1)));
+        assert!(!board.candidates((3, 3)).contains(&Number::Nine));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+    }
+
+    #[test]
+    fn chess_moves_propagate_and_solve() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::AntiKnight));
+        assert!(board.add_constraint(Constraint::AntiKing));
+        assert_eq!(28, board.find_neighbor_locations((4, 4)).len());
+
+        assert!(board.try_collapse(Number::One, (0, 0)));
+        assert!(!board.candidates((2, 1)).contains(&Number::One));
+        assert!(board.candidates((3, 3)).contains(&Number::One));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+        for row in 0..9 {
+            for column in 0..9 {
+                for neighbor in Constraint::AntiKnight.neighbors(&solution, (row, column)) {
+                    assert_ne!(solution.number((row, column)), solution.number(neighbor));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use super::{lines, options, unsupported};
+use crate::{Board, Number};
+
+/// Whether `locations` run in a straight line from one edge of the board to the other, moving
+/// along a row or column (or diagonally if `diagonal` is set) one square at a time.
+pub(super) fn crosses_board(size: usize, locations: &[(usize, usize)], diagonal: bool) -> bool {
+    let [first, second, ..] = locations else {
+        return false;
+    };
+    let step = (
+        second.0 as isize - first.0 as isize,
+        second.1 as isize - first.1 as isize,
+    );
+    let valid_step = if diagonal {
+        step.0.abs() == 1 && step.1.abs() == 1
+    } else {
+        step.0.abs() + step.1.abs() == 1
+    };
+    let follows_step = locations.windows(2).all(|pair| {
+        (
+            pair[1].0 as isize - pair[0].0 as isize,
+            pair[1].1 as isize - pair[0].1 as isize,
+        ) == step
+    });
+    let off_board = |location: &(usize, usize), sign: isize| {
+        let row = location.0 as isize + sign * step.0;
+        let column = location.1 as isize + sign * step.1;
+        !(0..size as isize).contains(&row) || !(0..size as isize).contains(&column)
+    };
+
+    valid_step
+        && follows_step
+        && off_board(first, -1)
+        && off_board(&locations[locations.len() - 1], 1)
+}
+
+/// Removes every candidate on a sandwiched row or column that does not fit any way of placing
+/// the lowest and highest numbers with squares between them that could add up to `sum`.
+pub(super) fn sandwich_eliminations(
+    board: &Board,
+    sum: usize,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let size = board.size();
+    let (lowest, highest) = (Number::ALL[0], Number::ALL[size - 1]);
+    let options: Vec<_> = locations
+        .iter()
+        .map(|&location| options(board, location))
+        .collect();
+    let mut supported = vec![vec![false; size]; locations.len()];
+
+    for start in 0..locations.len() {
+        for end in start + 1..locations.len() {
+            for (first, last) in [(lowest, highest), (highest, lowest)] {
+                if !options[start].contains(&first) || !options[end].contains(&last) {
+                    continue;
+                }
+
+                // The smallest and largest of the numbers strictly between the lowest and
+                // highest that each square in the middle could hold.
+                let Some(middle_bounds) = options[start + 1..end]
+                    .iter()
+                    .map(|numbers| {
+                        let mut filling = numbers
+                            .iter()
+                            .map(|number| number.value())
+                            .filter(|&value| value != 1 && value != size);
+                        let low = filling.next()?;
+                        Some((low, filling.next_back().unwrap_or(low)))
+                    })
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+                let count = end - start - 1;
+                let fewest = count * (count + 3) / 2;
+                let most = count * (2 * size - count - 1) / 2;
+                let lowest_sum: usize = middle_bounds.iter().map(|(low, _high)| low).sum();
+                let highest_sum: usize = middle_bounds.iter().map(|(_low, high)| high).sum();
+                if sum < fewest.max(lowest_sum) || sum > most.min(highest_sum) {
+                    continue;
+                }
+
+                supported[start][first.index()] = true;
+                supported[end][last.index()] = true;
+                for (i, &(low, high)) in middle_bounds.iter().enumerate() {
+                    for number in &options[start + 1 + i] {
+                        let value = number.value();
+                        if value != 1
+                            && value != size
+                            && value + lowest_sum - low <= sum
+                            && value + highest_sum - high >= sum
+                        {
+                            supported[start + 1 + i][number.index()] = true;
+                        }
+                    }
+                }
+                for i in (0..start).chain(end + 1..locations.len()) {
+                    for number in &options[i] {
+                        if *number != lowest && *number != highest {
+                            supported[i][number.index()] = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    unsupported(board, locations, &supported)
+}
+
+/// Removes the candidates that would hide too many skyscrapers: a square `i` squares in from
+/// the clue can be at most `size - count + 1 + i` tall, since every visible skyscraper after it
+/// must be taller still. A count of one means the tallest stands first.
+pub(super) fn skyscraper_eliminations(
+    board: &Board,
+    count: usize,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    let size = board.size();
+
+    locations
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &location)| {
+            board
+                .candidates(location)
+                .into_iter()
+                .filter(move |number| {
+                    number.value() + count > size + 1 + i
+                        || (count == 1 && i == 0 && number.value() != size)
+                })
+                .map(move |number| (location, number))
+        })
+        .collect()
+}
+
+/// Removes every candidate on a little killer diagonal that would take its sum past `sum`.
+pub(super) fn little_killer_eliminations(
+    board: &Board,
+    sum: usize,
+    locations: &[(usize, usize)],
+) -> Vec<((usize, usize), Number)> {
+    lines::bounded_sum_eliminations(board, locations, sum, sum)
+}
+
+/// Whether the collapsed lowest and highest numbers on a row or column already hold more than
+/// `sum` between them, or something else once the squares between them are full.
+pub(super) fn is_sandwich_broken(board: &Board, sum: usize, locations: &[(usize, usize)]) -> bool {
+    let numbers: Vec<_> = locations
+        .iter()
+        .map(|&location| board.number(location))
+        .collect();
+    let find = |value: usize| {
+        numbers
+            .iter()
+            .position(|number| number.is_some_and(|number| number.value() == value))
+    };
+    let (Some(first), Some(second)) = (find(1), find(board.size())) else {
+        return false;
+    };
+
+    let between = &numbers[first.min(second) + 1..first.max(second)];
+    let total: usize = between.iter().flatten().map(|number| number.value()).sum();
+
+    total > sum || (between.iter().all(Option::is_some) && total != sum)
+}
+
+/// Whether the collapsed skyscrapers nearest the clue already show more than `count`, or a full
+/// row or column shows anything other than `count`.
+pub(super) fn is_skyscraper_broken(
+    board: &Board,
+    count: usize,
+    locations: &[(usize, usize)],
+) -> bool {
+    let mut tallest = 0;
+    let mut visible = 0;
+    for &location in locations {
+        let Some(number) = board.number(location) else {
+            return false;
+        };
+        if number.value() > tallest {
+            tallest = number.value();
+            visible += 1;
+            if visible > count {
+                return true;
+            }
+        }
+    }
+
+    visible != count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Number};
+
+    use pretty_assertions::assert_eq;
+
+    fn row(row: usize) -> Vec<(usize, usize)> {
+        (0..9).map(|column| (row, column)).collect()
+    }
+
+    #[test]
+    fn sandwiches_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Sandwich {
+            sum: 0,
+            locations: row(0),
+        }));
+        assert!(board.try_collapse(Number::One, (0, 4)));
+        assert!(board.candidates((0, 3)).contains(&Number::Nine));
+        assert!(!board.candidates((0, 0)).contains(&Number::Nine));
+
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Sandwich {
+            sum: 35,
+            locations: row(0),
+        }));
+        assert_eq!(vec![Number::One, Number::Nine], board.candidates((0, 0)));
+        assert!(!board.candidates((0, 4)).contains(&Number::One));
+    }
+
+    #[test]
+    fn skyscrapers_limit_candidates() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Skyscraper {
+            count: 1,
+            locations: row(0),
+        }));
+        assert_eq!(vec![Number::Nine], board.candidates((0, 0)));
+
+        assert!(board.add_constraint(Constraint::Skyscraper {
+            count: 3,
+            locations: row(1).into_iter().rev().collect(),
+        }));
+        assert_eq!(Number::ALL[..7].to_vec(), board.candidates((1, 8)));
+        assert_eq!(Number::ALL[..8].to_vec(), board.candidates((1, 7)));
+    }
+
+    #[test]
+    fn little_killers_limit_candidates() {
+        let mut board = Board::default();
+        assert!(!board.add_constraint(Constraint::LittleKiller {
+            sum: 6,
+            locations: vec![(1, 0), (2, 1)],
+        }));
+        assert!(board.add_constraint(Constraint::LittleKiller {
+            sum: 6,
+            locations: vec![(2, 0), (1, 1), (0, 2)],
+        }));
+        assert_eq!(Number::ALL[..4].to_vec(), board.candidates((1, 1)));
+    }
+
+    #[test]
+    fn outside_clues_solve() {
+        let mut board = Board::new(2, 2).unwrap();
+        for (row, sum) in [5, 0, 0, 5].into_iter().enumerate() {
+            assert!(board.add_constraint(Constraint::Sandwich {
+                sum,
+                locations: (0..4).map(|column| (row, column)).collect(),
+            }));
+        }
+        assert!(board.add_constraint(Constraint::Skyscraper {
+            count: 3,
+            locations: (0..4).map(|row| (row, 0)).collect(),
+        }));
+        assert!(board.add_constraint(Constraint::LittleKiller {
+            sum: 5,
+            locations: vec![(1, 0), (0, 1)],
+        }));
+
+        assert_eq!(1, board.count_solutions(2));
+
+        let solved = board.solve().unwrap();
+        for (row, values) in [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]]
+            .iter()
+            .enumerate()
+        {
+            for (column, &value) in values.iter().enumerate() {
+                assert_eq!(Some(Number::ALL[value - 1]), solved.number((row, column)));
+            }
+        }
+    }
+}
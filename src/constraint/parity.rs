@@ -0,0 +1,61 @@
+use crate::{Board, Number};
+
+/// Removes every candidate in `locations` whose value is not even (or odd, if `even` is false).
+pub(super) fn parity_eliminations(
+    board: &Board,
+    locations: &[(usize, usize)],
+    even: bool,
+) -> Vec<((usize, usize), Number)> {
+    locations
+        .iter()
+        .flat_map(|&location| {
+            board
+                .candidates(location)
+                .into_iter()
+                .filter(move |number| (number.value() % 2 == 0) != even)
+                .map(move |number| (location, number))
+        })
+        .collect()
+}
+
+/// Whether a collapsed number in `locations` is odd when it should be even, or the other way
+/// around.
+pub(super) fn is_parity_broken(board: &Board, locations: &[(usize, usize)], even: bool) -> bool {
+    locations
+        .iter()
+        .filter_map(|&location| board.number(location))
+        .any(|number| (number.value() % 2 == 0) != even)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn shaded_squares_keep_their_parity() {
+        let mut board = Board::default();
+        assert!(board.add_constraint(Constraint::Even(vec![(0, 0), (4, 4)])));
+        assert!(board.add_constraint(Constraint::Odd(vec![(8, 8)])));
+        assert_eq!(
+            vec![Number::Two, Number::Four, Number::Six, Number::Eight],
+            board.candidates((4, 4))
+        );
+        assert_eq!(
+            vec![
+                Number::One,
+                Number::Three,
+                Number::Five,
+                Number::Seven,
+                Number::Nine,
+            ],
+            board.candidates((8, 8))
+        );
+        assert!(!board.clone().try_collapse(Number::Three, (0, 0)));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_valid());
+        assert_eq!(0, solution.number((0, 0)).unwrap().value() % 2);
+    }
+}
@@ -0,0 +1,344 @@
+use super::{json::Json, lz_string};
+use crate::{Board, Constraint, Dot, Number};
+
+impl Board {
+    /// Reads a puzzle in f-puzzles' JSON format, either as the JSON itself, as the compressed
+    /// text f-puzzles and SudokuPad put in their links, or as a whole link such as
+    /// `https://www.f-puzzles.com/?load=N4Ig...` or `https://sudokupad.app/fpuzzlesN4Ig...`.
+    ///
+    /// Givens and other numbers, irregular regions, killer cages, extra regions, thermometers,
+    /// arrows, palindromes, Kropki dots, XV, odd and even squares, diagonals, anti-knight and
+    /// anti-king are read. Rules Superdoku doesn't have are left out, so the board may allow
+    /// solutions the original puzzle doesn't. Returns None if the puzzle can't be read, or its
+    /// numbers break its rules.
+    pub fn from_fpuzzles(text: &str) -> Option<Self> {
+        let text = text.trim();
+        let json = if text.starts_with('{') {
+            text.to_string()
+        } else {
+            let data = match (text.find("load="), text.find("fpuzzles")) {
+                (Some(start), _) => &text[start + "load=".len()..],
+                (None, Some(start)) => &text[start + "fpuzzles".len()..],
+                (None, None) => text,
+            };
+            let data = data.split(['&', '#']).next().unwrap_or_default();
+            lz_string::decompress_from_base64(&percent_decode(data))?
+        };
+        let puzzle = Json::parse(&json)?;
+
+        let size = puzzle.get("size")?.as_usize()?;
+        let box_height = (1..=size)
+            .rev()
+            .find(|height| size % height == 0 && height * height <= size)?;
+        let mut board = Self::new(box_height, size / box_height)?;
+
+        let grid = puzzle.get("grid")?.elements();
+        if grid.len() != size || grid.iter().any(|row| row.elements().len() != size) {
+            return None;
+        }
+        let cells = || {
+            grid.iter().enumerate().flat_map(move |(i, row)| {
+                row.elements()
+                    .iter()
+                    .enumerate()
+                    .map(move |(column, cell)| ((size - 1 - i, column), cell))
+            })
+        };
+
+        // Each square's region is its box unless the puzzle says otherwise.
+        let mut regions = vec![Vec::new(); size];
+        for ((row, column), cell) in cells() {
+            let region = match cell.get("region") {
+                Some(region) => region.as_usize()?,
+                None => (size - 1 - row) / box_height * box_height + column / (size / box_height),
+            };
+            regions.get_mut(region)?.push((row, column));
+        }
+        let is_jigsaw = regions.iter().any(|region| {
+            region
+                .iter()
+                .any(|&location| board.find_box(location) != board.find_box(region[0]))
+        });
+        if is_jigsaw && !board.replace_boxes(regions) {
+            return None;
+        }
+
+        for constraint in constraints(&puzzle, size)? {
+            if !board.constraints.contains(&constraint) && !board.add_constraint(constraint) {
+                return None;
+            }
+        }
+
+        for given in [true, false] {
+            for (location, cell) in cells() {
+                let Some(value) = cell.get("value") else {
+                    continue;
+                };
+                let number = Number::from_index(value.as_usize()?.checked_sub(1)?)?;
+                if cell.get("given").is_some_and(Json::is_true) != given {
+                    continue;
+                }
+
+                let placed = if given {
+                    board.try_give(number, location)
+                } else {
+                    board.try_collapse(number, location)
+                };
+                if !placed {
+                    return None;
+                }
+            }
+        }
+
+        Some(board)
+    }
+}
+
+/// Every rule of an f-puzzles puzzle that Superdoku has, or None if one of them refers to
+/// squares that aren't on the board.
+fn constraints(puzzle: &Json, size: usize) -> Option<Vec<Constraint>> {
+    let entries = |key: &str| puzzle.get(key).map(Json::elements).unwrap_or_default();
+    let cells = |entry: &Json, key: &str| -> Option<Vec<(usize, usize)>> {
+        entry
+            .get(key)?
+            .elements()
+            .iter()
+            .map(|cell| location(cell.as_str()?, size))
+            .collect()
+    };
+    let lines = |entry: &Json| -> Option<Vec<Vec<(usize, usize)>>> {
+        entry
+            .get("lines")?
+            .elements()
+            .iter()
+            .map(|line| {
+                line.elements()
+                    .iter()
+                    .map(|cell| location(cell.as_str()?, size))
+                    .collect()
+            })
+            .collect()
+    };
+    let mut constraints = Vec::new();
+
+    if puzzle.get("diagonal+").is_some_and(Json::is_true) {
+        constraints.push(Constraint::diagonals(size)[0].clone());
+    }
+    if puzzle.get("diagonal-").is_some_and(Json::is_true) {
+        constraints.push(Constraint::diagonals(size)[1].clone());
+    }
+    if puzzle.get("antiknight").is_some_and(Json::is_true) {
+        constraints.push(Constraint::AntiKnight);
+    }
+    if puzzle.get("antiking").is_some_and(Json::is_true) {
+        constraints.push(Constraint::AntiKing);
+    }
+
+    for cage in entries("killercage") {
+        let locations = cells(cage, "cells")?;
+        // A cage without a sum only keeps its numbers apart.
+        match cage.get("value").and_then(Json::as_usize) {
+            Some(sum) => constraints.push(Constraint::Cage { sum, locations }),
+            None => constraints.push(Constraint::Region(locations)),
+        }
+    }
+    for region in entries("extraregion") {
+        constraints.push(Constraint::Region(cells(region, "cells")?));
+    }
+
+    for thermometer in entries("thermometer") {
+        constraints.extend(lines(thermometer)?.into_iter().map(Constraint::Thermometer));
+    }
+    for palindrome in entries("palindrome") {
+        constraints.extend(lines(palindrome)?.into_iter().map(Constraint::Palindrome));
+    }
+    for arrow in entries("arrow") {
+        // Arrows with a circle of more than one square have no counterpart here.
+        let [circle] = cells(arrow, "cells")?[..] else {
+            continue;
+        };
+        let line: Vec<_> = lines(arrow)?
+            .into_iter()
+            .flatten()
+            .filter(|&location| location != circle)
+            .collect();
+        constraints.push(Constraint::Arrow { circle, line });
+    }
+
+    let dots = [("difference", Dot::White, 1), ("ratio", Dot::Black, 2)];
+    for (key, dot, default) in dots {
+        for entry in entries(key) {
+            // Dots for other differences and ratios have no counterpart here.
+            if entry
+                .get("value")
+                .and_then(Json::as_usize)
+                .unwrap_or(default)
+                != default
+            {
+                continue;
+            }
+            let [first, second] = cells(entry, "cells")?[..] else {
+                return None;
+            };
+            constraints.push(Constraint::Dot {
+                dot,
+                locations: [first.min(second), first.max(second)],
+            });
+        }
+    }
+    for entry in entries("xv") {
+        let dot = match entry.get("value").and_then(Json::as_str) {
+            Some("X" | "x") => Dot::X,
+            Some("V" | "v") => Dot::V,
+            _ => continue,
+        };
+        let [first, second] = cells(entry, "cells")?[..] else {
+            return None;
+        };
+        constraints.push(Constraint::Dot {
+            dot,
+            locations: [first.min(second), first.max(second)],
+        });
+    }
+    for negative in entries("negative") {
+        match negative.as_str() {
+            Some("difference") => constraints.push(Constraint::AllGiven(Dot::White)),
+            Some("ratio") => constraints.push(Constraint::AllGiven(Dot::Black)),
+            Some("xv") => {
+                constraints.push(Constraint::AllGiven(Dot::X));
+                constraints.push(Constraint::AllGiven(Dot::V));
+            }
+            _ => (),
+        }
+    }
+
+    let shaded = |key: &str| -> Option<Vec<(usize, usize)>> {
+        entries(key)
+            .iter()
+            .map(|entry| location(entry.get("cell")?.as_str()?, size))
+            .collect()
+    };
+    let (even, odd) = (shaded("even")?, shaded("odd")?);
+    if !even.is_empty() {
+        constraints.push(Constraint::Even(even));
+    }
+    if !odd.is_empty() {
+        constraints.push(Constraint::Odd(odd));
+    }
+
+    Some(constraints)
+}
+
+/// Reads a cell written like `R1C1`, counting rows from the top and both from one.
+fn location(cell: &str, size: usize) -> Option<(usize, usize)> {
+    let (row, column) = cell.strip_prefix(['R', 'r'])?.split_once(['C', 'c'])?;
+    let (row, column): (usize, usize) = (row.parse().ok()?, column.parse().ok()?);
+    if !(1..=size).contains(&row) || !(1..=size).contains(&column) {
+        return None;
+    }
+
+    Some((size - row, column - 1))
+}
+
+/// Undoes the `%2B`-style escapes a link may have added to the compressed text.
+fn percent_decode(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('%') {
+        decoded.push_str(&rest[..start]);
+        let escape = rest
+            .get(start + 1..start + 3)
+            .and_then(|code| u8::from_str_radix(code, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte as char);
+                rest = &rest[start + 3..];
+            }
+            None => {
+                decoded.push('%');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Dot, Number};
+
+    use pretty_assertions::assert_eq;
+
+    const PUZZLE: &str = concat!(
+        r#"{"size":4,"grid":[[{"value":1,"given":true},{},{},{}],[{},{},{},{}],"#,
+        r#"[{},{},{},{}],[{},{},{},{"value":1}]],"#,
+        r#""killercage":[{"cells":["R2C1","R2C2"],"value":"7"}],"#,
+        r#""thermometer":[{"lines":[["R3C2","R2C2"]]}],"#,
+        r#""arrow":[{"cells":["R4C1"],"lines":[["R4C1","R4C2","R3C2"]]}],"#,
+        r#""difference":[{"cells":["R1C1","R1C2"]}],"ratio":[{"cells":["R2C4","R1C4"]}],"#,
+        r#""xv":[{"cells":["R2C2","R3C2"],"value":"V"}],"#,
+        r#""extraregion":[{"cells":["R1C4","R2C3","R3C1","R4C2"]}],"#,
+        r#""minimum":[{"cell":"R4C4"}]}"#,
+    );
+
+    #[test]
+    fn fpuzzles_variants_are_read() {
+        let board = Board::from_fpuzzles(PUZZLE).unwrap();
+        assert!(board.is_given((3, 0)));
+        assert_eq!(Some(Number::One), board.number((0, 3)));
+        assert!(!board.is_given((0, 3)));
+        assert_eq!(
+            &[
+                Constraint::Cage {
+                    sum: 7,
+                    locations: vec![(2, 0), (2, 1)],
+                },
+                Constraint::Region(vec![(3, 3), (2, 2), (1, 0), (0, 1)]),
+                Constraint::Thermometer(vec![(1, 1), (2, 1)]),
+                Constraint::Arrow {
+                    circle: (0, 0),
+                    line: vec![(0, 1), (1, 1)],
+                },
+                Constraint::Dot {
+                    dot: Dot::White,
+                    locations: [(3, 0), (3, 1)],
+                },
+                Constraint::Dot {
+                    dot: Dot::Black,
+                    locations: [(2, 3), (3, 3)],
+                },
+                Constraint::Dot {
+                    dot: Dot::V,
+                    locations: [(1, 1), (2, 1)],
+                },
+            ],
+            &board.constraints()[3..]
+        );
+
+        let solution = board.solve().unwrap();
+        assert_eq!(Some(Number::Four), solution.number((0, 0)));
+    }
+
+    #[test]
+    fn fpuzzles_links_are_decompressed() {
+        let data = "N4IgzglgXgpiBcAWANCA5gJwgEwQbT1ADcBDAGwFc54BGVNCImAOwQBcMqBfZYHv3lwC6yQv3GCRYwTL5SBC/iFKVqNYSJABrCGTIwMAYxJpqhEIZh6w+EACUATAGEaIVI6cOQmlVQQgAdhBhVDYACwMAWwB7SJg2A3xQMghmGBt4AnsAZk83e2cvISEQkBIMDGiAdySLKzIMvHtEF29UFLTGprsW13cWr3dcopLNbAgAMwmDFktay2tbOxpW9xWi0owSNgho+frGgqdEfOXj71KADyJ9xcyjwZy8n3I/eBAANWDNGEuOcpgDGirEyoAWDSWKxO7mc2VOuT6zWepUiqQgkQokVuZH8PXOwi4QA==";
+        let board = Board::from_fpuzzles(PUZZLE).unwrap();
+        assert_eq!(
+            Some(&board),
+            Board::from_fpuzzles(&format!("https://www.f-puzzles.com/?load={data}")).as_ref()
+        );
+        assert_eq!(
+            Some(&board),
+            Board::from_fpuzzles(&format!("https://sudokupad.app/fpuzzles{data}")).as_ref()
+        );
+        assert_eq!(None, Board::from_fpuzzles(&data[..40]));
+        assert_eq!(None, Board::from_fpuzzles("not a puzzle"));
+        // Nesting too deep for the stack is refused rather than followed.
+        assert_eq!(
+            None,
+            Board::from_fpuzzles(&format!("{}1", r#"{"a":["#.repeat(100_000)))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use super::parse_row;
+use crate::{Board, Number};
+
+impl Board {
+    /// Reads a puzzle in HoDoKu's library format, a line of fields separated by colons such as
+    /// `:0000:x:<puzzle>:<deleted candidates>::`. The puzzle lists every square from the top
+    /// left, with `.` or `0` for empty squares and `+` in front of numbers placed while solving,
+    /// which are kept apart from the givens. The deleted candidates are written as a number
+    /// followed by its row (counting from the top) and column, separated by spaces, and stay
+    /// deleted when numbers are undone. Returns None if no field holds a puzzle, its numbers
+    /// break the rules, or a square has every candidate deleted.
+    pub fn from_hodoku(text: &str) -> Option<Self> {
+        let fields: Vec<_> = text.trim().split(':').collect();
+        let (index, mut board) = fields
+            .iter()
+            .enumerate()
+            .find_map(|(index, field)| Some((index, Self::from_hodoku_puzzle(field)?)))?;
+        let size = board.size();
+
+        for deleted in fields.get(index + 1).unwrap_or(&"").split_whitespace() {
+            let numbers: Vec<_> = deleted.chars().map(Number::from_char).collect();
+            let [Some(number), Some(row), Some(column)] = numbers[..] else {
+                return None;
+            };
+            if row.value() > size || column.value() > size {
+                return None;
+            }
+
+            let location = (size - row.value(), column.index());
+            if board.number(location).is_some() {
+                continue;
+            }
+            let remaining: Vec<_> = board
+                .candidates(location)
+                .into_iter()
+                .filter(|&candidate| candidate != number)
+                .collect();
+            if !board.eliminate(location, &remaining) {
+                return None;
+            }
+        }
+
+        Some(board)
+    }
+
+    /// Writes the board in HoDoKu's library format, with numbers placed while solving marked with
+    /// `+` and every candidate that the numbers alone don't rule out listed as deleted.
+    pub fn to_hodoku(&self) -> String {
+        let size = self.size();
+        let mut unrestricted = self.clone();
+        unrestricted
+            .eliminated
+            .iter_mut()
+            .flatten()
+            .for_each(Vec::clear);
+        unrestricted.update_superpositions();
+
+        let mut puzzle = String::new();
+        let mut deleted = Vec::new();
+        for (i, row) in (0..size).rev().enumerate() {
+            for column in 0..size {
+                let location = (row, column);
+                match self.number(location) {
+                    Some(number) => {
+                        if !self.is_given(location) {
+                            puzzle.push('+');
+                        }
+                        puzzle.push(number.to_char());
+                    }
+                    None => {
+                        puzzle.push('.');
+
+                        let candidates = self.candidates(location);
+                        for number in unrestricted.candidates(location) {
+                            if !candidates.contains(&number) {
+                                deleted.push(format!("{number}{}{}", i + 1, column + 1));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        format!(":0000:x:{puzzle}:{}::", deleted.join(" "))
+    }
+
+    /// Reads the puzzle field of HoDoKu's library format.
+    fn from_hodoku_puzzle(field: &str) -> Option<Self> {
+        let mut givens = Vec::new();
+        let mut entries = Vec::new();
+        let mut entry = false;
+        for c in field.chars() {
+            if c == '+' {
+                entry = true;
+                continue;
+            }
+
+            let number = parse_row(&c.to_string())?.into_iter().next()?;
+            givens.push(number.filter(|_| !entry));
+            entries.push(number.filter(|_| entry));
+            entry = false;
+        }
+
+        let size = (1..=givens.len()).find(|size| size * size >= givens.len())?;
+        let rows = |squares: &[Option<Number>]| -> Vec<Vec<_>> {
+            squares.chunks(size).map(<[_]>::to_vec).collect()
+        };
+
+        let mut board = Self::from_given_rows(&rows(&givens))?;
+        board.place_rows(&rows(&entries), false).then_some(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hodoku_lines_round_trip() {
+        let puzzle =
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+        let mut board = Board::from_hodoku(&format!(":0000:x:{puzzle}:::")).unwrap();
+        assert!(board.is_given((8, 0)));
+
+        assert!(board.try_collapse(Number::Four, (8, 2)));
+        let line = board.to_hodoku();
+        assert!(line.starts_with(":0000:x:53+4.7....6..195..."));
+        assert_eq!(board, Board::from_hodoku(&line).unwrap());
+
+        let line = line.replace(":::", ":223::");
+        let mut read = Board::from_hodoku(&line).unwrap();
+        assert_eq!(vec![Number::Seven], read.candidates((7, 2)));
+        assert!(!read.is_given((8, 2)));
+        assert_eq!(line, read.to_hodoku());
+
+        // Deleted candidates don't come back when a number is undone.
+        assert!(read.undo((8, 2)));
+        assert_eq!(vec![Number::Four, Number::Seven], read.candidates((7, 2)));
+        assert!(read.to_hodoku().ends_with(":223::"));
+        assert_eq!(
+            None,
+            Board::from_hodoku(&line.replace(":223:", ":223 723:"))
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use std::{iter::Peekable, str::Chars};
+
+/// How deeply arrays and objects may be nested, which keeps deeply nested text from using up the
+/// stack. Puzzles never come close.
+const MAX_DEPTH: usize = 64;
+
+/// A JSON value, read just well enough to pick puzzles out of other programs' files.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+impl Json {
+    /// Reads a whole JSON document, or None if it isn't valid JSON.
+    pub(super) fn parse(text: &str) -> Option<Self> {
+        let mut chars = text.chars().peekable();
+        let value = parse_value(&mut chars, 0)?;
+        skip_whitespace(&mut chars);
+
+        chars.next().is_none().then_some(value)
+    }
+
+    /// The value of `key`, if this is an object with that key.
+    pub(super) fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Self::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// The elements of an array, or nothing for anything else.
+    pub(super) fn elements(&self) -> &[Self] {
+        match self {
+            Self::Array(elements) => elements,
+            _ => &[],
+        }
+    }
+
+    pub(super) fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// The value as a whole number, which may also be written as a string.
+    pub(super) fn as_usize(&self) -> Option<usize> {
+        match self {
+            Self::Number(number) if *number >= 0.0 && number.fract() == 0.0 => {
+                Some(*number as usize)
+            }
+            Self::String(string) => string.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Whether the value is `true`.
+    pub(super) fn is_true(&self) -> bool {
+        *self == Self::Bool(true)
+    }
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Reads the value starting at `chars`, nested `depth` arrays and objects deep.
+fn parse_value(chars: &mut Peekable<Chars>, depth: usize) -> Option<Json> {
+    skip_whitespace(chars);
+
+    match *chars.peek()? {
+        '{' | '[' if depth == MAX_DEPTH => None,
+        '{' => {
+            chars.next();
+            let mut entries = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&'}').is_some() {
+                return Some(Json::Object(entries));
+            }
+            loop {
+                skip_whitespace(chars);
+                if chars.next()? != '"' {
+                    return None;
+                }
+                let key = parse_string(chars)?;
+                skip_whitespace(chars);
+                if chars.next()? != ':' {
+                    return None;
+                }
+                entries.push((key, parse_value(chars, depth + 1)?));
+
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => (),
+                    '}' => return Some(Json::Object(entries)),
+                    _ => return None,
+                }
+            }
+        }
+        '[' => {
+            chars.next();
+            let mut elements = Vec::new();
+            skip_whitespace(chars);
+            if chars.next_if_eq(&']').is_some() {
+                return Some(Json::Array(elements));
+            }
+            loop {
+                elements.push(parse_value(chars, depth + 1)?);
+
+                skip_whitespace(chars);
+                match chars.next()? {
+                    ',' => (),
+                    ']' => return Some(Json::Array(elements)),
+                    _ => return None,
+                }
+            }
+        }
+        '"' => {
+            chars.next();
+            parse_string(chars).map(Json::String)
+        }
+        't' | 'f' | 'n' => {
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(char::is_ascii_alphabetic) {
+                word.push(c);
+            }
+
+            match word.as_str() {
+                "true" => Some(Json::Bool(true)),
+                "false" => Some(Json::Bool(false)),
+                "null" => Some(Json::Null),
+                _ => None,
+            }
+        }
+        _ => {
+            let mut number = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_ascii_digit() || "+-.eE".contains(*c)) {
+                number.push(c);
+            }
+
+            number.parse().ok().map(Json::Number)
+        }
+    }
+}
+
+/// Reads the rest of a string whose opening quote has already been read.
+fn parse_string(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut string = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(string),
+            '\\' => string.push(match chars.next()? {
+                'b' => '\u{8}',
+                'f' => '\u{c}',
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                'u' => {
+                    let code: String = (0..4).map(|_| chars.next()).collect::<Option<_>>()?;
+                    char::from_u32(u32::from_str_radix(&code, 16).ok()?)
+                        .unwrap_or(char::REPLACEMENT_CHARACTER)
+                }
+                c => c,
+            }),
+            c => string.push(c),
+        }
+    }
+}
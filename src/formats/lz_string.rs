@@ -0,0 +1,132 @@
+/// The characters of base64, in the order of the values they stand for.
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Undoes `LZString.compressToBase64`, which f-puzzles and SudokuPad use to fit puzzles into
+/// links. Returns None if `text` isn't base64 or doesn't decompress to valid text.
+pub(super) fn decompress_from_base64(text: &str) -> Option<String> {
+    let values = text
+        .bytes()
+        .filter(|&byte| byte != b'=' && !byte.is_ascii_whitespace())
+        .map(|byte| {
+            BASE64
+                .iter()
+                .position(|&c| c == byte)
+                .map(|value| value as u32)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    decompress(&values, 6)
+}
+
+/// Reads bits from the start of each value, `width` bits per value.
+struct Bits<'a> {
+    values: &'a [u32],
+    width: u32,
+    index: usize,
+    position: u32,
+}
+impl Bits<'_> {
+    /// Reads the next `count` bits, lowest bit first.
+    fn read(&mut self, count: u32) -> Option<u32> {
+        let mut bits = 0;
+        for power in 0..count {
+            let value = *self.values.get(self.index)?;
+            if value & (1 << (self.width - 1 - self.position)) != 0 {
+                bits |= 1 << power;
+            }
+
+            self.position += 1;
+            if self.position == self.width {
+                self.position = 0;
+                self.index += 1;
+            }
+        }
+
+        Some(bits)
+    }
+}
+
+/// The LZ-string decompression itself. The text is built from UTF-16 code units as in
+/// JavaScript, where the format comes from.
+fn decompress(values: &[u32], width: u32) -> Option<String> {
+    let mut bits = Bits {
+        values,
+        width,
+        index: 0,
+        position: 0,
+    };
+    // The first three codes mean an 8-bit character, a 16-bit character and the end of the
+    // text, so the dictionary starts with three placeholders.
+    let mut dictionary: Vec<Vec<u16>> = vec![Vec::new(); 3];
+    let mut enlarge_in = 4;
+    let mut code_width = 3;
+
+    let first = match bits.read(2)? {
+        0 => bits.read(8)?,
+        1 => bits.read(16)?,
+        _ => return None,
+    };
+    let mut previous = vec![first as u16];
+    dictionary.push(previous.clone());
+    let mut result = previous.clone();
+
+    loop {
+        let mut code = bits.read(code_width)? as usize;
+        match code {
+            0 | 1 => {
+                let character = bits.read(if code == 0 { 8 } else { 16 })?;
+                dictionary.push(vec![character as u16]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return String::from_utf16(&result).ok(),
+            _ => (),
+        }
+        if enlarge_in == 0 {
+            enlarge_in = 1 << code_width;
+            code_width += 1;
+        }
+
+        let entry = match dictionary.get(code) {
+            Some(entry) => entry.clone(),
+            None if code == dictionary.len() => {
+                let mut entry = previous.clone();
+                entry.push(previous[0]);
+                entry
+            }
+            None => return None,
+        };
+        result.extend(&entry);
+
+        let mut next = previous;
+        next.push(entry[0]);
+        dictionary.push(next);
+        enlarge_in -= 1;
+        previous = entry;
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << code_width;
+            code_width += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn text_is_decompressed_from_base64() {
+        // LZString.compressToBase64("hello")
+        assert_eq!(
+            Some("hello".to_string()),
+            decompress_from_base64("BYUwNmD2Q===")
+        );
+        assert_eq!(None, decompress_from_base64("BYUwNmD2"));
+        assert_eq!(None, decompress_from_base64("BYU*"));
+        // The first code can only be a character.
+        assert_eq!(None, decompress_from_base64("wAAA"));
+    }
+}
@@ -0,0 +1,105 @@
+mod fpuzzles;
+mod hodoku;
+mod json;
+mod lz_string;
+mod sadman;
+mod simple_sudoku;
+
+use crate::{Board, Number};
+
+impl Board {
+    /// An empty board of the given size with square boxes, or None if the size is not a square
+    /// number. The formats of other Sudoku programs only know about plain Sudoku, so this is the
+    /// board they are all read into.
+    fn with_size(size: usize) -> Option<Self> {
+        let box_size = (1..=size).find(|box_size| box_size * box_size >= size)?;
+        if box_size * box_size != size {
+            return None;
+        }
+
+        Self::new(box_size, box_size)
+    }
+
+    /// Reads a board from rows of squares written from the top, placing each number as a given.
+    /// Returns None if the rows don't make up a board or the givens break its rules.
+    fn from_given_rows(rows: &[Vec<Option<Number>>]) -> Option<Self> {
+        let mut board = Self::with_size(rows.len())?;
+        board.place_rows(rows, true).then_some(board)
+    }
+
+    /// Places each number in `rows`, written from the top, on an empty square, as a given or as
+    /// an entry. Returns false if the rows don't fit the board or a number can't be placed.
+    fn place_rows(&mut self, rows: &[Vec<Option<Number>>], given: bool) -> bool {
+        let size = self.size();
+        if rows.len() != size || rows.iter().any(|row| row.len() != size) {
+            return false;
+        }
+
+        for (i, row) in rows.iter().enumerate() {
+            for (column, number) in row.iter().enumerate() {
+                let location = (size - 1 - i, column);
+                let Some(number) = *number else {
+                    continue;
+                };
+
+                let placed = match self.number(location) {
+                    Some(existing) => existing == number,
+                    None if given => self.try_give(number, location),
+                    None => self.try_collapse(number, location),
+                };
+                if !placed {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Every row from the top, holding the givens and, if `entries` is set, the numbers placed
+    /// while solving too.
+    fn number_rows(&self, entries: bool) -> Vec<Vec<Option<Number>>> {
+        let size = self.size();
+
+        (0..size)
+            .rev()
+            .map(|row| {
+                (0..size)
+                    .map(|column| {
+                        let location = (row, column);
+                        self.number(location)
+                            .filter(|_| entries || self.is_given(location))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Whether any number on the board was placed while solving rather than given.
+    fn has_entries(&self) -> bool {
+        let size = self.size();
+
+        (0..size)
+            .flat_map(|row| (0..size).map(move |column| (row, column)))
+            .any(|location| self.number(location).is_some() && !self.is_given(location))
+    }
+}
+
+/// Reads a row of squares, where `.` and `0` stand for an empty square. Returns None if any
+/// character is neither a number nor empty.
+fn parse_row(row: &str) -> Option<Vec<Option<Number>>> {
+    row.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '.' | '0' => Some(None),
+            c => Number::from_char(c).map(Some),
+        })
+        .collect()
+}
+
+/// Writes a row of squares, with `empty` standing for an empty square.
+fn write_row(row: &[Option<Number>], empty: char) -> String {
+    row.iter()
+        .map(|number| number.map_or(empty, Number::to_char))
+        .collect()
+}
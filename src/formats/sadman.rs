@@ -0,0 +1,133 @@
+use super::{parse_row, write_row};
+use crate::Board;
+
+impl Board {
+    /// Reads a puzzle in SadMan Sudoku's `.sdk` format: one line per row with `.` for empty
+    /// squares, after any `#` lines of information about the puzzle. A `[Puzzle]` section holds
+    /// the givens, and an optional `[State]` section the numbers placed so far. Returns None if
+    /// the text is not a puzzle or its numbers break the rules.
+    pub fn from_sdk(text: &str) -> Option<Self> {
+        let mut puzzle = Vec::new();
+        let mut state = Vec::new();
+        let mut in_state = false;
+
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match line {
+                "[Puzzle]" => in_state = false,
+                "[State]" => in_state = true,
+                _ if in_state => state.push(parse_row(line)?),
+                _ => puzzle.push(parse_row(line)?),
+            }
+        }
+
+        let mut board = Self::from_given_rows(&puzzle)?;
+        if !state.is_empty() && !board.place_rows(&state, false) {
+            return None;
+        }
+
+        Some(board)
+    }
+
+    /// Writes the board in SadMan Sudoku's `.sdk` format. If any numbers have been placed besides
+    /// the givens, the givens are written in a `[Puzzle]` section and every number in a
+    /// `[State]` section.
+    pub fn to_sdk(&self) -> String {
+        let rows = |entries: bool| -> String {
+            self.number_rows(entries)
+                .iter()
+                .map(|row| format!("{}\n", write_row(row, '.')))
+                .collect()
+        };
+
+        if self.has_entries() {
+            format!("[Puzzle]\n{}[State]\n{}", rows(false), rows(true))
+        } else {
+            rows(false)
+        }
+    }
+
+    /// Reads a collection of puzzles in the `.sdm` format, one per line, with each line holding
+    /// every square of the puzzle from the top left and `0` or `.` for empty squares. Returns
+    /// None if any line is not a puzzle.
+    pub fn from_sdm(text: &str) -> Option<Vec<Self>> {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                let squares = parse_row(line)?;
+                let size = (1..=squares.len()).find(|size| size * size >= squares.len())?;
+                let rows: Vec<_> = squares.chunks(size).map(<[_]>::to_vec).collect();
+
+                Self::from_given_rows(&rows)
+            })
+            .collect()
+    }
+
+    /// Writes the givens of each board in the `.sdm` format, one puzzle per line.
+    pub fn to_sdm(boards: &[Self]) -> String {
+        boards
+            .iter()
+            .map(|board| {
+                let squares: Vec<_> = board.number_rows(false).concat();
+                format!("{}\n", write_row(&squares, '0'))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Number};
+
+    use pretty_assertions::assert_eq;
+
+    const PUZZLE: &str = "\
+53..7....
+6..195...
+.98....6.
+8...6...3
+4..8.3..1
+7...2...6
+.6....28.
+...419..5
+....8..79
+";
+
+    #[test]
+    fn sdk_keeps_givens_and_entries_apart() {
+        let text = format!("#AAnonymous\n#DA classic\n{PUZZLE}");
+        let mut board = Board::from_sdk(&text).unwrap();
+        assert!(board.is_given((8, 0)));
+        assert_eq!(Some(Number::Five), board.number((8, 0)));
+        assert_eq!(PUZZLE, board.to_sdk());
+
+        assert!(board.try_collapse(Number::Four, (8, 2)));
+        let saved = board.to_sdk();
+        assert!(saved.starts_with("[Puzzle]\n53..7....\n"));
+        assert!(saved.contains("[State]\n534.7....\n"));
+
+        let read = Board::from_sdk(&saved).unwrap();
+        assert_eq!(board, read);
+        assert!(!read.is_given((8, 2)));
+        assert!(Board::from_sdk("55.......\n").is_none());
+    }
+
+    #[test]
+    fn sdm_holds_one_puzzle_per_line() {
+        let line: String = PUZZLE.lines().collect();
+        let text = format!("{line}\n\n{}\n", line.replace('.', "0"));
+        let boards = Board::from_sdm(&text).unwrap();
+        assert_eq!(2, boards.len());
+        assert_eq!(boards[0], boards[1]);
+
+        assert_eq!(
+            format!("{}\n", line.replace('.', "0")),
+            Board::to_sdm(&boards[..1])
+        );
+        assert!(Board::from_sdm("123").is_none());
+    }
+}
@@ -0,0 +1,88 @@
+use super::{parse_row, write_row};
+use crate::Board;
+
+impl Board {
+    /// Reads a puzzle in Simple Sudoku's `.ss` format, where every row is a line with `|` between
+    /// boxes and `.` for empty squares, and lines of `-`, `+` and `*` mark the edges of the boxes.
+    /// The lines between boxes may be left out. Every number is read as a given. Returns None if
+    /// the text is not a puzzle or its numbers break the rules.
+    pub fn from_ss(text: &str) -> Option<Self> {
+        let rows = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.chars().all(|c| "*-+|".contains(c)))
+            .map(|line| parse_row(&line.replace('|', "")))
+            .collect::<Option<Vec<_>>>()?;
+
+        Self::from_given_rows(&rows)
+    }
+
+    /// Writes the givens in Simple Sudoku's `.ss` format.
+    pub fn to_ss(&self) -> String {
+        let stacks = self.size() / self.box_width;
+        //   *-----------*
+        let edge = format!("*{}*\n", "-".repeat(self.size() + stacks - 1));
+        //   |---+---+---|
+        let between = format!("|{}|\n", vec!["-".repeat(self.box_width); stacks].join("+"));
+
+        let mut text = edge.clone();
+        for (i, row) in self.number_rows(false).iter().enumerate() {
+            if i > 0 && i % self.box_height == 0 {
+                text.push_str(&between);
+            }
+
+            //   |53.|.7.|...|
+            let boxes: Vec<_> = row
+                .chunks(self.box_width)
+                .map(|squares| write_row(squares, '.'))
+                .collect();
+            text.push_str(&format!("|{}|\n", boxes.join("|")));
+        }
+        text.push_str(&edge);
+
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ss_grids_round_trip() {
+        let text = [
+            "*-----------*\n",
+            "|53.|.7.|...|\n",
+            "|6..|195|...|\n",
+            "|.98|...|.6.|\n",
+            "|---+---+---|\n",
+            "|8..|.6.|..3|\n",
+            "|4..|8.3|..1|\n",
+            "|7..|.2.|..6|\n",
+            "|---+---+---|\n",
+            "|.6.|...|28.|\n",
+            "|...|419|..5|\n",
+            "|...|.8.|.79|\n",
+            "*-----------*\n",
+        ]
+        .concat();
+
+        let mut board = Board::from_ss(&text).unwrap();
+        assert!(board.is_given((0, 8)));
+        assert_eq!(Some(Number::Nine), board.number((0, 8)));
+        assert!(board.try_collapse(Number::Four, (8, 2)));
+        assert_eq!(text, board.to_ss());
+
+        let plain: String = text
+            .lines()
+            .filter(|line| !line.contains('-'))
+            .map(|line| format!("{}\n", line.replace('|', "")))
+            .collect();
+        assert_eq!(
+            Board::from_ss(&text).unwrap(),
+            Board::from_ss(&plain).unwrap()
+        );
+    }
+}
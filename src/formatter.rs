@@ -0,0 +1,408 @@
+use crate::{Board, PencilMarks};
+
+use std::fmt::Display;
+
+/// How the rows and columns of a board written by a [`BoardFormatter`] are labelled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Labels {
+    /// Rows numbered beside the board and columns lettered under it, as on a chessboard.
+    #[default]
+    Chess,
+    /// Rows labelled `R1`, `R2` and so on beside the board, and columns `C1`, `C2` and so on
+    /// above it.
+    RowColumn,
+    /// No labels at all.
+    Hidden,
+}
+
+/// Writes a board as text in a style of your choosing, which can be set up one option at a time
+/// starting from [`Board::formatter`]. Left as it starts, it writes the board exactly as
+/// displaying it does, with row 9 at the top and columns lettered `a` to `i`.
+///
+/// Whichever way round the board is written, its rows keep the same numbers: the row
+/// [`Board::number`] calls row 0 is always labelled 1. Cages, jigsaw regions and clues outside
+/// the board are only drawn in the starting style, and the other styles draw the plain grid.
+#[derive(Clone, Copy, Debug)]
+pub struct BoardFormatter<'a> {
+    board: &'a Board,
+    row_one_at_top: bool,
+    labels: Labels,
+    compact: bool,
+    unicode: bool,
+    candidates: bool,
+    pencil_marks: Option<&'a PencilMarks>,
+}
+impl<'a> BoardFormatter<'a> {
+    pub fn new(board: &'a Board) -> Self {
+        Self {
+            board,
+            row_one_at_top: false,
+            labels: Labels::Chess,
+            compact: false,
+            unicode: false,
+            candidates: false,
+            pencil_marks: None,
+        }
+    }
+
+    /// Writes row 1 at the top instead of the bottom.
+    pub fn row_one_at_top(mut self, row_one_at_top: bool) -> Self {
+        self.row_one_at_top = row_one_at_top;
+        self
+    }
+
+    pub fn labels(mut self, labels: Labels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Leaves out the borders, labels and the marks of variant rules, so that each row of the
+    /// board is a single line with a space between boxes.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Draws the borders with Unicode box-drawing characters instead of `|` and `-`.
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// Writes the candidates of every unsolved square in a small grid shaped like a box, such as
+    /// 3x3 on a 9x9 board, with each number where it would be on a keypad and a `.` in place of
+    /// every number ruled out. Solved squares show their number in the middle.
+    pub fn candidates(mut self, candidates: bool) -> Self {
+        self.candidates = candidates;
+        self
+    }
+
+    /// Writes the player's pencil marks in every unsolved square instead of its candidates: its
+    /// corner marks followed by a `/` and its center marks, or a `.` if it has none. Squares are
+    /// widened to fit the most marks any square has.
+    ///
+    /// # Panics
+    ///
+    /// If the marks are for a board of a different size.
+    pub fn pencil_marks(mut self, pencil_marks: &'a PencilMarks) -> Self {
+        assert_eq!(
+            self.board.size(),
+            pencil_marks.size(),
+            "Pencil marks must be for a board of the same size"
+        );
+        self.pencil_marks = Some(pencil_marks);
+        self
+    }
+
+    /// Whether unsolved squares show their candidates in a small grid.
+    fn shows_candidates(&self) -> bool {
+        self.candidates && self.pencil_marks.is_none()
+    }
+
+    /// The lines a square is written as, each as wide as the others.
+    fn square(&self, location: (usize, usize)) -> Vec<String> {
+        let board = self.board;
+        if let (Some(pencil_marks), None) = (self.pencil_marks, board.number(location)) {
+            return vec![pencil_marks.written(location)];
+        }
+        if !self.shows_candidates() {
+            return vec![board.get(location).to_string()];
+        }
+
+        let (rows, columns) = (board.box_height, board.box_width);
+        let number = board.number(location);
+        let candidates = board.candidates(location);
+        (0..rows)
+            .map(|i| {
+                (0..columns)
+                    .map(|j| match number {
+                        Some(number) if (i, j) == (rows / 2, columns / 2) => number.to_char(),
+                        Some(_) => ' ',
+                        None => candidates
+                            .iter()
+                            .find(|number| number.index() == i * columns + j)
+                            .map_or('.', |number| number.to_char()),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The characters a border is drawn with: its fill, its left end, where it crosses the
+    /// borders between boxes and its right end, for the border above the first row, a border
+    /// between boxes or the border below the last row.
+    fn border_characters(&self, top: bool, bottom: bool) -> [char; 4] {
+        match (self.unicode, top, bottom) {
+            (false, _, _) => ['-', '|', '|', '|'],
+            (true, true, _) => ['─', '┌', '┬', '┐'],
+            (true, _, true) => ['─', '└', '┴', '┘'],
+            (true, _, _) => ['─', '├', '┼', '┤'],
+        }
+    }
+}
+impl Display for BoardFormatter<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let board = self.board;
+        if !self.row_one_at_top
+            && self.labels == Labels::Chess
+            && !self.compact
+            && !self.unicode
+            && !self.candidates
+            && self.pencil_marks.is_none()
+        {
+            return board.fmt(f);
+        }
+
+        let size = board.size();
+        let stacks = size / board.box_width;
+        let rows: Vec<usize> = if self.row_one_at_top {
+            (0..size).collect()
+        } else {
+            (0..size).rev().collect()
+        };
+
+        // Every square by row and then column, and how wide the widest of them is.
+        let squares: Vec<Vec<Vec<String>>> = (0..size)
+            .map(|row| (0..size).map(|column| self.square((row, column))).collect())
+            .collect();
+        let widest = squares
+            .iter()
+            .flatten()
+            .flatten()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(1);
+
+        if self.compact {
+            // Pencil marks take up different widths, so squares are padded and spaced apart.
+            let (gap, stack_gap) = match self.pencil_marks {
+                Some(_) => (" ", "  "),
+                None => ("", " "),
+            };
+            for &row in &rows {
+                for line in 0..squares[row][0].len() {
+                    let stacks: Vec<String> = squares[row]
+                        .chunks(board.box_width)
+                        .map(|stack| {
+                            let stack: Vec<_> = stack
+                                .iter()
+                                .map(|square| format!("{:widest$}", square[line]))
+                                .collect();
+                            stack.join(gap)
+                        })
+                        .collect();
+                    writeln!(f, "{}", stacks.join(stack_gap).trim_end())?;
+                }
+            }
+
+            return Ok(());
+        }
+
+        let row_label = |row: usize| match self.labels {
+            Labels::Chess => (row + 1).to_string(),
+            Labels::RowColumn => format!("R{}", row + 1),
+            Labels::Hidden => String::new(),
+        };
+        let column_label = |column: usize| match self.labels {
+            Labels::Chess => ((b'a' + column as u8) as char).to_string(),
+            Labels::RowColumn => format!("C{}", column + 1),
+            Labels::Hidden => String::new(),
+        };
+        let label_width = (0..size).map(|row| row_label(row).len()).max().unwrap_or(0);
+        let vertical = if self.unicode { '│' } else { '|' };
+        // Each square is followed by the marks of the variant rules it is part of, or a space, and
+        // is widened to fit its column's label above it.
+        let labels: Vec<_> = (0..size).map(column_label).collect();
+        let label_length = labels.iter().map(String::len).max().unwrap_or(0);
+        let content_width = widest.max(label_length);
+        let square_width = content_width + 1;
+        let stack_width = board.box_width * square_width;
+
+        let border = |top: bool, bottom: bool| {
+            let [fill, left, middle, right] = self.border_characters(top, bottom);
+            let mut line = format!("{} {left}", " ".repeat(label_width));
+            for stack in 0..stacks {
+                line.extend(std::iter::repeat_n(fill, stack_width + 1));
+                line.push(if stack + 1 == stacks { right } else { middle });
+            }
+            line
+        };
+        let column_labels = || {
+            let mut line = " ".repeat(label_width + 3);
+            for (stack, labels) in labels.chunks(board.box_width).enumerate() {
+                if stack > 0 {
+                    line.push_str("  ");
+                }
+                for label in labels {
+                    line.push_str(&format!("{label:square_width$}"));
+                }
+            }
+            line
+        };
+
+        let mut lines = Vec::new();
+        if self.labels == Labels::RowColumn {
+            lines.push(column_labels());
+        }
+        lines.push(border(true, false));
+        for (i, &row) in rows.iter().enumerate() {
+            let squares: Vec<Vec<String>> = squares[row]
+                .iter()
+                .enumerate()
+                .map(|(column, square)| {
+                    let marker = if self.shows_candidates() {
+                        ' '
+                    } else {
+                        board.marker((row, column))
+                    };
+                    square
+                        .iter()
+                        .map(|line| format!("{line:content_width$}{marker}"))
+                        .collect()
+                })
+                .collect();
+            let height = squares[0].len();
+            for line in 0..height {
+                let label = if line == height / 2 {
+                    row_label(row)
+                } else {
+                    String::new()
+                };
+                let mut text = format!("{label:>label_width$} {vertical} ");
+                for stack in squares.chunks(board.box_width) {
+                    for square in stack {
+                        text.push_str(&square[line]);
+                    }
+                    text.push(vertical);
+                    text.push(' ');
+                }
+                lines.push(text);
+            }
+
+            if i + 1 == size {
+                lines.push(border(false, true));
+            } else if (i + 1) % board.box_height == 0 {
+                lines.push(border(false, false));
+            } else if self.shows_candidates() {
+                // A line between the rows of candidates keeps squares above and below apart.
+                let mut text = format!("{} {vertical}", " ".repeat(label_width));
+                for _ in 0..stacks {
+                    text.push_str(&" ".repeat(stack_width + 1));
+                    text.push(vertical);
+                }
+                lines.push(text);
+            }
+        }
+        if self.labels == Labels::Chess {
+            lines.push(column_labels());
+        }
+
+        for line in lines {
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Board {
+    /// A formatter for writing the board in a style other than the one it is displayed in.
+    pub fn formatter(&self) -> BoardFormatter<'_> {
+        BoardFormatter::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Labels, Number};
+
+    use pretty_assertions::assert_eq;
+
+    fn board() -> Board {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (0, 0)));
+        assert!(board.try_collapse(Number::Two, (3, 3)));
+        board
+    }
+
+    #[test]
+    fn formatters_start_out_like_display() {
+        let board = board();
+        assert_eq!(board.to_string(), board.formatter().to_string());
+    }
+
+    #[test]
+    fn formatters_can_put_row_one_at_the_top() {
+        let text = board()
+            .formatter()
+            .row_one_at_top(true)
+            .labels(Labels::RowColumn)
+            .to_string();
+        assert_eq!(
+            [
+                // The columns are widened to fit their labels.
+                "     C1 C2   C3 C4\n",
+                "   |-------|-------|\n",
+                "R1 | 1  ?  | ?  ?  |\n",
+                "R2 | ?  ?  | ?  ?  |\n",
+                "   |-------|-------|\n",
+                "R3 | ?  ?  | ?  ?  |\n",
+                "R4 | ?  ?  | ?  2  |\n",
+                "   |-------|-------|\n",
+            ]
+            .concat(),
+            text
+        );
+    }
+
+    #[test]
+    fn formatters_can_be_compact_or_unicode() {
+        let board = board();
+        assert_eq!(
+            "?? ?2\n?? ??\n?? ??\n1? ??\n",
+            board.formatter().compact(true).to_string()
+        );
+        assert_eq!(
+            [
+                "  ┌─────┬─────┐\n",
+                "4 │ ? ? │ ? 2 │\n",
+                "3 │ ? ? │ ? ? │\n",
+                "  ├─────┼─────┤\n",
+                "2 │ ? ? │ ? ? │\n",
+                "1 │ 1 ? │ ? ? │\n",
+                "  └─────┴─────┘\n",
+                "    a b   c d\n",
+            ]
+            .concat(),
+            board.formatter().unicode(true).to_string()
+        );
+    }
+
+    #[test]
+    fn formatters_can_show_candidates_in_a_grid() {
+        let text = board()
+            .formatter()
+            .candidates(true)
+            .labels(Labels::Hidden)
+            .to_string();
+        assert_eq!(
+            [
+                " |-------|-------|\n",
+                " | .. 1. | 1.    |\n",
+                " | 34 34 | 34  2 |\n",
+                " |       |       |\n",
+                " | .2 12 | 1. 1. |\n",
+                " | 34 34 | 34 34 |\n",
+                " |-------|-------|\n",
+                " | .2 .2 | 12 1. |\n",
+                " | 34 34 | 34 34 |\n",
+                " |       |       |\n",
+                " |    .2 | .2 .. |\n",
+                " |  1 34 | 34 34 |\n",
+                " |-------|-------|\n",
+            ]
+            .concat(),
+            text
+        );
+    }
+}
@@ -1,5 +1,24 @@
+mod candidate_grid;
+mod constraint;
+mod formats;
+mod formatter;
+mod margin;
+mod multi;
+mod outline;
+mod pencil;
+mod render;
+#[cfg(feature = "serde")]
+mod schema;
+mod solver;
 mod square;
-
+mod transform;
+
+pub use constraint::{Constraint, Dot};
+pub use formatter::{BoardFormatter, Labels};
+pub use multi::MultiBoard;
+pub use pencil::{Mark, PencilMarks};
+pub use render::{Highlight, Theme};
+pub use solver::Difficulty;
 pub use square::Number;
 use square::Square;
 
@@ -7,11 +26,101 @@ use rand::prelude::*;
 
 use std::{cmp::Ordering, fmt::Display};
 
-#[derive(Clone, Debug)]
+/// A Sudoku board made of `size` by `size` squares, split into boxes of `box_height` rows and
+/// `box_width` columns. Which numbers may go where is decided by the board's [`Constraint`]s,
+/// which start out requiring every row, column and box to contain each of the first `size`
+/// numbers.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "schema::BoardData")
+)]
 pub struct Board {
-    board: [[Square; 9]; 9],
+    box_height: usize,
+    box_width: usize,
+    #[cfg_attr(feature = "serde", serde(rename = "squares"))]
+    board: Vec<Vec<Square>>,
+    constraints: Vec<Constraint>,
+    /// The candidates ruled out at each location by something other than the constraints, such
+    /// as candidates read from a file, which are ruled out again whenever the superpositions are
+    /// rebuilt.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    eliminated: Vec<Vec<Vec<Number>>>,
 }
 impl Board {
+    /// Creates an empty board with boxes of the given dimensions, such as 2 by 3 for a 6x6 board
+    /// or 4 by 4 for a 16x16 board. Returns None if the board would need more numbers than
+    /// [`Number::ALL`] provides.
+    pub fn new(box_height: usize, box_width: usize) -> Option<Self> {
+        let size = box_height * box_width;
+        if size == 0 || size > Number::ALL.len() {
+            return None;
+        }
+
+        Some(Self {
+            box_height,
+            box_width,
+            board: vec![vec![Square::new(size); size]; size],
+            constraints: vec![Constraint::Rows, Constraint::Columns, Constraint::Boxes],
+            eliminated: vec![vec![Vec::new(); size]; size],
+        })
+    }
+
+    /// Adds a constraint to the board and removes every candidate it rules out. Irregular boxes
+    /// take the place of the board's boxes, as through [`Board::replace_boxes`]. Returns false
+    /// without changing anything if the constraint refers to a location off the board, has
+    /// irregular boxes that don't tile the board, or is already on it.
+    pub fn add_constraint(&mut self, constraint: Constraint) -> bool {
+        if self.constraints.contains(&constraint) || !constraint.fits(self.size()) {
+            return false;
+        }
+        if let Constraint::Jigsaw(regions) = constraint {
+            return self.replace_boxes(regions);
+        }
+
+        self.constraints.push(constraint);
+        self.update_superpositions();
+
+        true
+    }
+
+    /// Whether the number at `location` was given as part of the puzzle rather than placed while
+    /// solving it.
+    pub fn is_given(&self, location: (usize, usize)) -> bool {
+        self.get(location).is_given()
+    }
+
+    /// The numbers that could still be placed at `location`, or nothing if it is already solved.
+    pub fn candidates(&self, location: (usize, usize)) -> Vec<Number> {
+        self.get(location).candidates()
+    }
+
+    /// The constraints deciding which numbers may go where on the board.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// The number collapsed at `location`, if any.
+    pub fn number(&self, location: (usize, usize)) -> Option<Number> {
+        self.get(location).collapsed_number()
+    }
+
+    /// The number of rows in a box.
+    pub fn box_height(&self) -> usize {
+        self.box_height
+    }
+
+    /// The number of columns in a box.
+    pub fn box_width(&self) -> usize {
+        self.box_width
+    }
+
+    /// The number of rows and columns on the board, which is also the number of numbers used.
+    pub fn size(&self) -> usize {
+        self.box_height * self.box_width
+    }
+
     pub fn is_solved(&self) -> bool {
         self.board
             .iter()
@@ -19,6 +128,14 @@ impl Board {
             .all(|square| square.collapsed_number().is_some())
     }
 
+    /// Whether the collapsed numbers on the board break none of its constraints.
+    pub fn is_valid(&self) -> bool {
+        !self
+            .constraints
+            .iter()
+            .any(|constraint| constraint.is_violated(self))
+    }
+
     pub fn random_collapse(&mut self) -> Option<(Number, (usize, usize))> {
         let mut rng = thread_rng();
 
@@ -34,8 +151,29 @@ impl Board {
         Some((number, location))
     }
 
+    /// Removes a constraint from the board and restores every candidate it alone ruled out.
+    /// Returns false if the constraint is not on the board.
+    pub fn remove_constraint(&mut self, constraint: &Constraint) -> bool {
+        let Some(index) = self
+            .constraints
+            .iter()
+            .position(|other| other == constraint)
+        else {
+            return false;
+        };
+
+        self.constraints.remove(index);
+        self.update_superpositions();
+
+        true
+    }
+
+    /// Clears every square on the board, keeping its constraints.
     pub fn reset(&mut self) {
-        *self = Self::default();
+        let size = self.size();
+        self.board = vec![vec![Square::new(size); size]; size];
+        self.eliminated = vec![vec![Vec::new(); size]; size];
+        self.apply_constraints();
     }
 
     pub fn try_collapse(&mut self, number: Number, location: (usize, usize)) -> bool {
@@ -48,19 +186,53 @@ impl Board {
         }
     }
 
+    /// Places `number` at `location` as one of the puzzle's givens, which unlike numbers placed
+    /// with [`Board::try_collapse`] can't be undone. Returns false if `number` can't go there.
+    pub fn try_give(&mut self, number: Number, location: (usize, usize)) -> bool {
+        if self.get_mut(location).try_give(number) {
+            self.propagate_collapse(number, location);
+
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn undo(&mut self, location: (usize, usize)) -> bool {
-        if !self.get_mut(location).undo_collapse() {
+        let size = self.size();
+        if !self.get_mut(location).undo_collapse(size) {
             return false;
         };
 
-        self.propagate_superposition(location);
+        self.update_superpositions();
 
         true
     }
 
+    /// Removes every candidate the constraints rule out beyond their neighbors, until none of
+    /// them rules out anything more.
+    fn apply_constraints(&mut self) {
+        loop {
+            let eliminations: Vec<_> = self
+                .constraints
+                .iter()
+                .flat_map(|constraint| constraint.eliminations(self))
+                .collect();
+
+            let mut changed = false;
+            for (location, number) in eliminations {
+                changed |= self.get_mut(location).remove(number);
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
     fn find_lowest_superpositions(&self) -> Option<Vec<(usize, usize)>> {
         let mut lowest_superpositions = Vec::new();
-        let mut lowest_number = 9;
+        let mut lowest_number = self.size();
 
         self.board.iter().enumerate().for_each(|(i, row)| {
             row.iter().enumerate().for_each(|(j, square)| {
@@ -110,116 +282,200 @@ impl Board {
     }
 
     fn propagate_collapse(&mut self, number: Number, location: (usize, usize)) {
-        for location in Self::find_neighbor_locations(location) {
+        for location in self.find_neighbor_locations(location) {
             self.get_mut(location).remove(number);
         }
-    }
 
-    fn propagate_superposition(&mut self, location: (usize, usize)) {
-        for neighbor in Self::find_neighbor_locations(location) {
-            if let Some(collapsed) = self.board[neighbor.0][neighbor.1].collapsed_number() {
-                self.get_mut(location).remove(collapsed);
-            } else {
-                self.update_superposition(neighbor);
-            }
-        }
+        self.apply_constraints();
     }
 
     fn update_superposition(&mut self, location: (usize, usize)) {
-        *self.get_mut(location) = Square::default();
+        *self.get_mut(location) = Square::new(self.size());
 
-        for neighbor in Self::find_neighbor_locations(location) {
+        for neighbor in self.find_neighbor_locations(location) {
             if let Some(collapsed_number) = self.get(neighbor).collapsed_number() {
                 self.get_mut(location).remove(collapsed_number);
             }
         }
+        for number in self.eliminated[location.0][location.1].clone() {
+            self.get_mut(location).remove(number);
+        }
     }
 
-    fn find_neighbor_locations(location: (usize, usize)) -> [(usize, usize); 20] {
-        let mut neighbors = [(0, 0); 20];
-        let mut neighbors_iter = neighbors.iter_mut();
+    /// Removes every candidate at `location` that is not in `allowed`, along with whatever that
+    /// rules out elsewhere. Returns whether any candidate was removed.
+    fn restrict(&mut self, location: (usize, usize), allowed: &[Number]) -> bool {
+        let mut changed = false;
+        for number in self.candidates(location) {
+            if !allowed.contains(&number) {
+                changed |= self.get_mut(location).remove(number);
+            }
+        }
 
-        let location_box = (location.0 / 3, location.1 / 3);
-        let location_box_corner = (location_box.0 * 3, location_box.1 * 3);
+        if changed {
+            self.apply_constraints();
+        }
 
-        // We find the neighbors in the same box.
-        for i in 0..3 {
-            for j in 0..3 {
-                let box_location = (location_box_corner.0 + i, location_box_corner.1 + j);
-                if box_location == location {
-                    continue;
-                }
+        changed
+    }
 
-                *neighbors_iter
-                    .next()
-                    .expect("Ran out of neighbor spaces while searching box") = box_location
+    /// Removes every candidate at `location` that is not in `allowed` the way [`Board::restrict`]
+    /// does, and remembers what was removed so that it stays ruled out when the superpositions
+    /// are rebuilt. Returns whether the square has any candidates left.
+    fn eliminate(&mut self, location: (usize, usize), allowed: &[Number]) -> bool {
+        let removed: Vec<_> = self
+            .candidates(location)
+            .into_iter()
+            .filter(|number| !allowed.contains(number))
+            .collect();
+        let eliminated = &mut self.eliminated[location.0][location.1];
+        for &number in &removed {
+            if let Err(index) = eliminated.binary_search(&number) {
+                eliminated.insert(index, number);
             }
         }
 
-        // We find the neighbors in the same row.
-        for j in 0..9 {
-            if location_box.1 == j / 3 {
-                continue;
-            }
+        self.restrict(location, allowed);
 
-            *neighbors_iter
-                .next()
-                .expect("Ran out of neighbor spaces while searching row") = (location.0, j);
-        }
+        !self.candidates(location).is_empty()
+    }
 
-        // We find the neighbors in the same column.
-        for i in 0..9 {
-            if location_box.0 == i / 3 {
-                continue;
+    /// Rebuilds the superposition of every unsolved square from scratch, which is needed whenever
+    /// a number is taken off the board or the constraints change.
+    fn update_superpositions(&mut self) {
+        for row in 0..self.size() {
+            for column in 0..self.size() {
+                if self.board[row][column].collapsed_number().is_none() {
+                    self.update_superposition((row, column));
+                }
             }
+        }
 
-            *neighbors_iter
-                .next()
-                .expect("Ran out of neighbor spaces while searching column") = (i, location.1)
+        self.apply_constraints();
+    }
+
+    /// The character shown after the square at `location` to point out the constraints it is
+    /// part of, or a space if there is nothing to point out.
+    fn marker(&self, location: (usize, usize)) -> char {
+        let mut markers = self
+            .constraints
+            .iter()
+            .filter_map(|constraint| constraint.marker(location));
+
+        match (markers.next(), markers.next()) {
+            (None, _) => ' ',
+            (Some(marker), None) => marker,
+            (Some(first), Some(second)) if first == second => first,
+            (Some(_), Some(_)) => 'X',
         }
+    }
+
+    /// Finds which box `location` is in, whether the boxes are regular or irregular, or None if
+    /// the board has no boxes.
+    fn find_box(&self, location: (usize, usize)) -> Option<usize> {
+        self.constraints
+            .iter()
+            .find_map(|constraint| match constraint {
+                Constraint::Boxes => Some(
+                    location.0 / self.box_height * (self.size() / self.box_width)
+                        + location.1 / self.box_width,
+                ),
+                Constraint::Jigsaw(regions) => {
+                    regions.iter().position(|region| region.contains(&location))
+                }
+                _ => None,
+            })
+    }
+
+    /// Finds every location that may not hold the same number as `location` under any of the
+    /// board's constraints.
+    fn find_neighbor_locations(&self, location: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut neighbors: Vec<_> = self
+            .constraints
+            .iter()
+            .flat_map(|constraint| constraint.neighbors(self, location))
+            .collect();
+
+        neighbors.sort_unstable();
+        neighbors.dedup();
 
         neighbors
     }
 }
 impl Default for Board {
     fn default() -> Self {
-        Self {
-            board: vec![vec![Square::default(); 9].try_into().unwrap(); 9]
-                .try_into()
-                .unwrap(),
-        }
+        Self::new(3, 3).expect("A 9x9 board is always valid")
     }
 }
 impl Display for Board {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut row_reversed_board_iter = self.board.iter().rev().flat_map(|row| row.iter());
+        if self
+            .constraints
+            .iter()
+            .any(|constraint| constraint.outside_clue().is_some())
+        {
+            return self.fmt_with_margin(f);
+        }
+
+        self.fmt_grid(f)
+    }
+}
+impl Board {
+    /// Draws the board itself, without any clues outside it.
+    fn fmt_grid(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self
+            .constraints
+            .iter()
+            .any(|constraint| matches!(constraint, Constraint::Cage { .. } | Constraint::Jigsaw(_)))
+        {
+            return self.fmt_outlined(f);
+        }
+
+        let size = self.size();
+        let label_width = size.to_string().len();
+
+        //           |-------|-------|-------|
+        let border = format!(
+            "{} |{} \n",
+            " ".repeat(label_width),
+            format!("{}|", "-".repeat(self.box_width * 2 + 1)).repeat(size / self.box_width)
+        );
 
-        f.write_str("                            \n")?;
-        f.write_str("  |-------|-------|-------| \n")?;
+        f.write_str(&format!("{}\n", " ".repeat(border.len() - 1)))?;
+        f.write_str(&border)?;
         //           9 | ? ? ? | ? ? ? | ? ? ? |
 
-        for box_row in 0..3 {
-            for row in 0..3 {
-                f.write_str(&format!("{} | ", 9 - ((box_row * 3) + row)))?;
-                for _square_triplet in 0..3 {
-                    for _square in 0..3 {
-                        f.write_str(&format!(
-                            "{} ",
-                            row_reversed_board_iter
-                                .next()
-                                .expect("Fatally failed to display board")
-                        ))?;
+        for box_row in 0..size / self.box_height {
+            for row in 0..self.box_height {
+                f.write_str(&format!(
+                    "{:>label_width$} | ",
+                    size - ((box_row * self.box_height) + row)
+                ))?;
+                let location_row = size - 1 - (box_row * self.box_height + row);
+                for stack in 0..size / self.box_width {
+                    for square in 0..self.box_width {
+                        let location = (location_row, stack * self.box_width + square);
+                        f.write_str(&format!("{}{}", self.get(location), self.marker(location)))?;
                     }
                     f.write_str("| ")?;
                 }
                 f.write_str("\n")?;
             }
             //           1 | ? ? ? | ? ? ? | ? ? ? |
-            f.write_str("  |-------|-------|-------| \n")?;
+            f.write_str(&border)?;
         }
 
-        //             |-------|-------|-------|
-        f.write_str("    a b c   d e f   g h i   \n")
+        //               a b c   d e f   g h i
+        f.write_str(&" ".repeat(label_width + 3))?;
+        for stack in 0..size / self.box_width {
+            let letters: Vec<_> = (0..self.box_width)
+                .map(|column| {
+                    ((b'a' + (stack * self.box_width + column) as u8) as char).to_string()
+                })
+                .collect();
+            f.write_str(&format!("{}   ", letters.join(" ")))?;
+        }
+        f.write_str("\n")
     }
 }
 
@@ -260,7 +516,32 @@ mod tests {
 
         assert_eq!(
             correct_neighbors,
-            HashSet::from(Board::find_neighbor_locations(location))
+            HashSet::from_iter(Board::default().find_neighbor_locations(location))
+        );
+    }
+
+    #[test]
+    fn find_neighbor_locations_handles_rectangular_boxes() {
+        // 6x6 board with boxes of 2 rows and 3 columns
+        let board = Board::new(2, 3).unwrap();
+        let correct_neighbors = HashSet::from([
+            (2, 3),
+            (2, 5),
+            (3, 3),
+            (3, 4),
+            (3, 5),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (0, 4),
+            (1, 4),
+            (4, 4),
+            (5, 4),
+        ]);
+
+        assert_eq!(
+            correct_neighbors,
+            HashSet::from_iter(board.find_neighbor_locations((2, 4)))
         );
     }
 }
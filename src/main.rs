@@ -1,7 +1,7 @@
 mod cli;
 mod gui;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 /// Superdoku, a Sudoku solver
 #[derive(Parser)]
@@ -10,12 +10,25 @@ pub struct Args {
     /// Use the command line interface version of Superdoku
     #[arg(short, long, default_value_t = true)]
     pub cli: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    Print(cli::print::Args),
 }
 
 fn main() {
     let args = Args::parse();
 
-    if args.cli {
+    if let Some(Command::Print(print)) = &args.command {
+        if let Err(error) = cli::print::main(print) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+    } else if args.cli {
         cli::main();
     } else {
         gui::main();
@@ -0,0 +1,214 @@
+use crate::Board;
+
+use std::fmt::Display;
+
+/// The board drawn without its outside clues, so that they can be fitted around it.
+struct Grid<'a>(&'a Board);
+impl Display for Grid<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt_grid(f)
+    }
+}
+
+impl Board {
+    /// Draws the board with a margin around it for the clues outside it. Clues beside a row are
+    /// written across, to the left or right of it, and clues above or below a column are written
+    /// downwards, one digit per line, so that clues in neighboring columns never run together. The
+    /// direction of a little killer clue stays beside its last digit.
+    pub(crate) fn fmt_with_margin(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = self.size() as isize;
+        let label_width = self.size().to_string().len();
+        let grid = Grid(self).to_string();
+        let mut lines: Vec<String> = grid.lines().map(str::to_owned).collect();
+
+        // The letters under the board show where each column is drawn.
+        let letters = lines.pop().unwrap_or_default();
+        let columns: Vec<_> = letters
+            .char_indices()
+            .filter(|(_, letter)| letter.is_ascii_lowercase())
+            .map(|(x, _)| x)
+            .collect();
+        let column_x = |column: isize| match column {
+            -1 => columns[0] - 2,
+            column if column == size => columns[columns.len() - 1] + 2,
+            column => columns[column as usize],
+        };
+        let row_line = |row: isize| {
+            lines.iter().position(|line| {
+                line.get(..label_width)
+                    .and_then(|label| label.trim().parse::<isize>().ok())
+                    == Some(row + 1)
+            })
+        };
+
+        let mut above: Vec<(usize, String)> = Vec::new();
+        let mut below: Vec<(usize, String)> = Vec::new();
+        let mut left: Vec<(usize, String)> = Vec::new();
+        let mut right: Vec<(usize, String)> = Vec::new();
+        for ((row, column), text) in self
+            .constraints
+            .iter()
+            .filter_map(|constraint| constraint.outside_clue())
+        {
+            if row == size {
+                above.push((column_x(column), text));
+            } else if row == -1 {
+                below.push((column_x(column), text));
+            } else if let Some(line) = row_line(row) {
+                if column == -1 {
+                    left.push((line, text));
+                } else {
+                    right.push((line, text));
+                }
+            }
+        }
+
+        let left_width = left
+            .iter()
+            .map(|(_, text)| text.len() + 1)
+            .max()
+            .unwrap_or(0);
+        let right_x = lines
+            .iter()
+            .map(|line| line.trim_end().len() + 1)
+            .max()
+            .unwrap_or(0);
+
+        for (i, line) in lines.iter_mut().enumerate() {
+            let texts: Vec<_> = left
+                .iter()
+                .filter(|(line, _)| *line == i)
+                .map(|(_, text)| text.as_str())
+                .collect();
+            *line = format!(
+                "{:>left_width$}{line}",
+                format!("{} ", texts.join(",")).trim_start()
+            );
+
+            let texts: Vec<_> = right
+                .iter()
+                .filter(|(line, _)| *line == i)
+                .map(|(_, text)| text.as_str())
+                .collect();
+            if !texts.is_empty() {
+                let width = right_x + left_width;
+                *line = format!("{:width$}{}", line.trim_end(), texts.join(","));
+            }
+        }
+
+        let first = lines.remove(0);
+        let width = first.chars().count();
+        let top = vertical(&above, left_width, width, true);
+        let bottom = vertical(&below, left_width, width, false);
+        let letters = format!("{}{letters}", " ".repeat(left_width));
+
+        for line in std::iter::once(first)
+            .chain(top)
+            .chain(lines)
+            .chain(bottom)
+            .chain(std::iter::once(letters))
+        {
+            writeln!(f, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes each clue downwards, one digit per line with any direction beside the last one, at the
+/// position it is paired with. Clues above the board end on the last line and clues below it
+/// start on the first, so that each one sits against the board. Every line is padded to `width`.
+fn vertical(clues: &[(usize, String)], indent: usize, width: usize, above: bool) -> Vec<String> {
+    let clues: Vec<_> = clues
+        .iter()
+        .map(|(x, text)| {
+            let mut pieces: Vec<String> = Vec::new();
+            for character in text.chars() {
+                match pieces.last_mut() {
+                    Some(last) if matches!(character, '/' | '\\') => last.push(character),
+                    _ => pieces.push(character.to_string()),
+                }
+            }
+            (x + indent, pieces)
+        })
+        .collect();
+    let height = clues
+        .iter()
+        .map(|(_, pieces)| pieces.len())
+        .max()
+        .unwrap_or(0);
+
+    (0..height)
+        .map(|i| {
+            let mut line: Vec<char> = Vec::new();
+            for (x, pieces) in &clues {
+                let offset = if above { height - pieces.len() } else { 0 };
+                let Some(piece) = i.checked_sub(offset).and_then(|i| pieces.get(i)) else {
+                    continue;
+                };
+
+                for (x, character) in (*x..).zip(piece.chars()) {
+                    if line.len() <= x {
+                        line.resize(x + 1, ' ');
+                    }
+                    line[x] = character;
+                }
+            }
+
+            format!("{:width$}", line.into_iter().collect::<String>())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn clues_fit_around_the_board() {
+        let mut board = Board::new(2, 2).unwrap();
+        for constraint in [
+            Constraint::Sandwich {
+                sum: 12,
+                locations: (0..4).map(|row| (row, 1)).collect(),
+            },
+            Constraint::Skyscraper {
+                count: 3,
+                locations: (0..4).rev().map(|row| (row, 2)).collect(),
+            },
+            Constraint::Skyscraper {
+                count: 2,
+                locations: (0..4).rev().map(|column| (2, column)).collect(),
+            },
+            Constraint::LittleKiller {
+                sum: 5,
+                locations: vec![(3, 2), (2, 1), (1, 0)],
+            },
+        ] {
+            assert!(board.add_constraint(constraint));
+        }
+
+        // Spaces are shown as dots to make the padding visible.
+        assert_eq!(
+            [
+                "................",
+                "..........3.5/..",
+                "..|-----|-----|.",
+                "4.|.?.0.|.?.?.|.",
+                "3.|.?.0.|.?.?.|.2",
+                "..|-----|-----|.",
+                "2.|.?.0.|.?.?.|.",
+                "1.|.?.0.|.?.?.|.",
+                "..|-----|-----|.",
+                "......1.........",
+                "......2.........",
+                "....a.b...c.d...",
+            ]
+            .map(|line| line.to_owned() + "\n")
+            .concat(),
+            board.to_string().replace(' ', ".")
+        );
+    }
+}
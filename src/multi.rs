@@ -0,0 +1,510 @@
+use crate::{Board, Constraint, Number};
+
+use std::fmt::Display;
+
+/// How many numbers [`MultiBoard::solve`] tries placing before giving up.
+const MAX_GUESSES: usize = 10_000;
+
+/// A puzzle made of several boards overlapping on a larger grid, such as Samurai Sudoku, where
+/// the squares two boards share must hold the same number in both. Locations are `(row, column)`
+/// on the larger grid, with row 0 at the bottom as on a [`Board`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "crate::schema::MultiBoardData")
+)]
+pub struct MultiBoard {
+    grids: Vec<Board>,
+    offsets: Vec<(usize, usize)>,
+}
+impl MultiBoard {
+    /// Creates empty boards with boxes of the given dimensions, placing the bottom left square of
+    /// each at the given offset. Returns None if there are no boards, the boxes are too big for
+    /// [`Board::new`], or an offset doesn't line the boards' boxes up with each other.
+    pub fn new(box_height: usize, box_width: usize, offsets: &[(usize, usize)]) -> Option<Self> {
+        let aligned = offsets
+            .iter()
+            .all(|offset| offset.0 % box_height == 0 && offset.1 % box_width == 0);
+        if offsets.is_empty() || !aligned {
+            return None;
+        }
+
+        Some(Self {
+            grids: vec![Board::new(box_height, box_width)?; offsets.len()],
+            offsets: offsets.to_vec(),
+        })
+    }
+
+    /// Puts boards together at the given offsets, then carries what each rules out through the
+    /// shared squares. Returns None if there are no boards, their boxes differ, an offset doesn't
+    /// line the boxes up, or two boards hold different numbers in a square they share.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_grids(grids: Vec<Board>, offsets: Vec<(usize, usize)>) -> Option<Self> {
+        let first = grids.first()?;
+        let (box_height, box_width) = (first.box_height(), first.box_width());
+        let same_boxes = grids
+            .iter()
+            .all(|grid| (grid.box_height(), grid.box_width()) == (box_height, box_width));
+        if grids.len() != offsets.len() || !same_boxes {
+            return None;
+        }
+
+        let mut multi = Self::new(box_height, box_width, &offsets)?;
+        multi.grids = grids;
+        let agreed = (0..multi.height())
+            .flat_map(|row| (0..multi.width()).map(move |column| (row, column)))
+            .all(|location| {
+                let mut numbers = multi
+                    .placements(location)
+                    .map(|(grid, local)| multi.grids[grid].number(local));
+                let first = numbers.next();
+                numbers.all(|number| Some(number) == first)
+            });
+        if !agreed {
+            return None;
+        }
+        multi.synchronize();
+
+        Some(multi)
+    }
+
+    /// Samurai Sudoku: four 9x9 boards in the corners of a 21x21 grid, each sharing its inner
+    /// corner box with a fifth board in the middle.
+    pub fn samurai() -> Self {
+        Self::new(3, 3, &[(12, 0), (12, 12), (6, 6), (0, 0), (0, 12)])
+            .expect("Samurai boxes are always aligned")
+    }
+
+    /// Twodoku: two 9x9 boards sharing a corner box.
+    pub fn twodoku() -> Self {
+        Self::new(3, 3, &[(6, 0), (0, 6)]).expect("Twodoku boxes are always aligned")
+    }
+
+    /// Sohei Sudoku: four 9x9 boards in a ring, each sharing a corner box with the two next to
+    /// it.
+    pub fn sohei() -> Self {
+        Self::new(3, 3, &[(12, 6), (6, 0), (6, 12), (0, 6)])
+            .expect("Sohei boxes are always aligned")
+    }
+
+    /// The boards making up the puzzle.
+    pub fn grids(&self) -> &[Board] {
+        &self.grids
+    }
+
+    /// Where the bottom left square of each board sits on the larger grid.
+    pub fn offsets(&self) -> &[(usize, usize)] {
+        &self.offsets
+    }
+
+    /// The number of rows on the larger grid.
+    pub fn height(&self) -> usize {
+        self.offsets
+            .iter()
+            .zip(&self.grids)
+            .map(|(offset, grid)| offset.0 + grid.size())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of columns on the larger grid.
+    pub fn width(&self) -> usize {
+        self.offsets
+            .iter()
+            .zip(&self.grids)
+            .map(|(offset, grid)| offset.1 + grid.size())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Whether any board covers `location`.
+    pub fn contains(&self, location: (usize, usize)) -> bool {
+        self.placements(location).next().is_some()
+    }
+
+    /// The numbers that could still be placed at `location`, or nothing if it is already solved
+    /// or no board covers it.
+    pub fn candidates(&self, location: (usize, usize)) -> Vec<Number> {
+        self.placements(location)
+            .next()
+            .map(|(grid, local)| self.grids[grid].candidates(local))
+            .unwrap_or_default()
+    }
+
+    /// The number collapsed at `location`, if any.
+    pub fn number(&self, location: (usize, usize)) -> Option<Number> {
+        self.placements(location)
+            .next()
+            .and_then(|(grid, local)| self.grids[grid].number(local))
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.grids.iter().all(Board::is_solved)
+    }
+
+    /// Whether the collapsed numbers on every board break none of its constraints.
+    pub fn is_valid(&self) -> bool {
+        self.grids.iter().all(Board::is_valid)
+    }
+
+    /// Collapses `location` on every board covering it, then carries what that rules out
+    /// through the shared squares. Returns false without changing anything if no board covers
+    /// `location` or `number` is not one of its candidates.
+    pub fn try_collapse(&mut self, number: Number, location: (usize, usize)) -> bool {
+        self.place(number, location, Board::try_collapse)
+    }
+
+    /// Places `number` at `location` on every board covering it as one of the puzzle's givens,
+    /// as with [`Board::try_give`], then carries what that rules out through the shared squares.
+    /// Returns false without changing anything if no board covers `location` or `number` is not
+    /// one of its candidates.
+    pub fn try_give(&mut self, number: Number, location: (usize, usize)) -> bool {
+        self.place(number, location, Board::try_give)
+    }
+
+    /// Adds a constraint to one of the boards, with locations on that board rather than the
+    /// larger grid, then carries what it rules out through the shared squares. Returns false
+    /// without changing anything if there is no such board or it refuses the constraint, as with
+    /// [`Board::add_constraint`].
+    pub fn add_constraint(&mut self, grid: usize, constraint: Constraint) -> bool {
+        let Some(board) = self.grids.get_mut(grid) else {
+            return false;
+        };
+        if !board.add_constraint(constraint) {
+            return false;
+        }
+        self.synchronize();
+
+        true
+    }
+
+    /// Takes the number at `location` off every board covering it. Returns false if there is
+    /// no number there.
+    pub fn undo(&mut self, location: (usize, usize)) -> bool {
+        let placements: Vec<_> = self.placements(location).collect();
+        let mut undone = false;
+        for (grid, local) in placements {
+            undone |= self.grids[grid].undo(local);
+        }
+        if !undone {
+            return false;
+        }
+
+        // Candidates a board lost through a shared square may have depended on the number just
+        // taken away, so every board starts over from its own numbers.
+        for grid in &mut self.grids {
+            grid.update_superpositions();
+        }
+        self.synchronize();
+
+        true
+    }
+
+    /// Finds a solution to the whole puzzle, if there is one. Gives up and returns None after
+    /// trying ten thousand numbers, since puzzles spanning several boards can take far longer to
+    /// search than one board.
+    pub fn solve(&self) -> Option<Self> {
+        let mut stack = vec![self.clone()];
+        let mut guesses = 0;
+
+        while let Some(multi) = stack.pop() {
+            let Some((location, candidates)) = multi.fewest_candidates() else {
+                if multi.is_valid() {
+                    return Some(multi);
+                }
+                continue;
+            };
+
+            // The stack is last in, first out, so the lowest candidate is pushed last.
+            for number in candidates.into_iter().rev() {
+                guesses += 1;
+                if guesses > MAX_GUESSES {
+                    return None;
+                }
+
+                let mut next = multi.clone();
+                if next.try_collapse(number, location) {
+                    stack.push(next);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The unsolved location with the fewest candidates left, along with them, or None if every
+    /// location is solved.
+    fn fewest_candidates(&self) -> Option<((usize, usize), Vec<Number>)> {
+        let mut fewest: Option<((usize, usize), Vec<Number>)> = None;
+        for row in 0..self.height() {
+            for column in 0..self.width() {
+                let location = (row, column);
+                if !self.contains(location) || self.number(location).is_some() {
+                    continue;
+                }
+
+                let candidates = self.candidates(location);
+                let fewer = match &fewest {
+                    Some((_, fewest)) => candidates.len() < fewest.len(),
+                    None => true,
+                };
+                if fewer {
+                    fewest = Some((location, candidates));
+                }
+            }
+        }
+
+        fewest
+    }
+
+    /// Places `number` with `collapse` on every board covering `location`, then synchronizes
+    /// the boards.
+    fn place(
+        &mut self,
+        number: Number,
+        location: (usize, usize),
+        collapse: fn(&mut Board, Number, (usize, usize)) -> bool,
+    ) -> bool {
+        let placements: Vec<_> = self.placements(location).collect();
+        let allowed = placements
+            .iter()
+            .all(|&(grid, local)| self.grids[grid].candidates(local).contains(&number));
+        if placements.is_empty() || !allowed {
+            return false;
+        }
+
+        for (grid, local) in placements {
+            collapse(&mut self.grids[grid], number, local);
+        }
+        self.synchronize();
+
+        true
+    }
+
+    /// Every board covering `location`, along with where `location` is on that board.
+    fn placements(
+        &self,
+        location: (usize, usize),
+    ) -> impl Iterator<Item = (usize, (usize, usize))> + '_ {
+        self.offsets.iter().zip(&self.grids).enumerate().filter_map(
+            move |(grid, (offset, board))| {
+                let local = (
+                    location.0.checked_sub(offset.0)?,
+                    location.1.checked_sub(offset.1)?,
+                );
+
+                (local.0 < board.size() && local.1 < board.size()).then_some((grid, local))
+            },
+        )
+    }
+
+    /// Keeps only the candidates every board sharing a square agrees on, until the boards stop
+    /// ruling out anything more.
+    fn synchronize(&mut self) {
+        let shared: Vec<_> = (0..self.height())
+            .flat_map(|row| (0..self.width()).map(move |column| (row, column)))
+            .filter(|&location| self.placements(location).nth(1).is_some())
+            .collect();
+
+        loop {
+            let mut changed = false;
+            for &location in &shared {
+                let placements: Vec<_> = self.placements(location).collect();
+                if placements
+                    .iter()
+                    .any(|&(grid, local)| self.grids[grid].number(local).is_some())
+                {
+                    continue;
+                }
+
+                let agreed: Vec<_> = Number::ALL
+                    .into_iter()
+                    .filter(|number| {
+                        placements.iter().all(|&(grid, local)| {
+                            self.grids[grid].candidates(local).contains(number)
+                        })
+                    })
+                    .collect();
+                for (grid, local) in placements {
+                    changed |= self.grids[grid].restrict(local, &agreed);
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Whether any board covers the box at the given box row and column of the larger grid.
+    fn covers_box(&self, box_row: Option<usize>, box_column: Option<usize>) -> bool {
+        let (Some(box_row), Some(box_column)) = (box_row, box_column) else {
+            return false;
+        };
+        let (box_height, box_width) = (self.grids[0].box_height(), self.grids[0].box_width());
+
+        self.contains((box_row * box_height, box_column * box_width))
+    }
+}
+impl Display for MultiBoard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (box_height, box_width) = (self.grids[0].box_height(), self.grids[0].box_width());
+        let (box_rows, box_columns) = (self.height() / box_height, self.width() / box_width);
+        let label_width = self.height().to_string().len();
+
+        // Box rows and columns are counted from the top left as drawn, so the box row of a line
+        // between boxes is the one below it.
+        let covered = |box_row: usize, box_column: usize| {
+            self.covers_box(
+                (box_row < box_rows).then(|| box_rows - 1 - box_row),
+                (box_column < box_columns).then_some(box_column),
+            )
+        };
+        let touches = |box_row: usize, box_column: usize| {
+            [box_row.checked_sub(1), Some(box_row)]
+                .into_iter()
+                .flatten()
+                .flat_map(|row| {
+                    [box_column.checked_sub(1), Some(box_column)]
+                        .into_iter()
+                        .flatten()
+                        .map(move |column| (row, column))
+                })
+                .any(|(row, column)| covered(row, column))
+        };
+
+        let mut lines = vec![String::new()];
+        for box_row in 0..=box_rows {
+            let mut line = " ".repeat(label_width + 1);
+            for box_column in 0..=box_columns {
+                line.push(if touches(box_row, box_column) {
+                    '|'
+                } else {
+                    ' '
+                });
+                if box_column < box_columns {
+                    let above = box_row > 0 && covered(box_row - 1, box_column);
+                    let fill = if above || covered(box_row, box_column) {
+                        '-'
+                    } else {
+                        ' '
+                    };
+                    line.push_str(&fill.to_string().repeat(box_width * 2 + 1));
+                }
+            }
+            lines.push(line);
+
+            if box_row == box_rows {
+                break;
+            }
+            for row in (0..box_height).rev() {
+                let location_row = (box_rows - 1 - box_row) * box_height + row;
+                let mut line = format!("{:>label_width$} ", location_row + 1);
+                for box_column in 0..=box_columns {
+                    let edge = (box_column > 0 && covered(box_row, box_column - 1))
+                        || covered(box_row, box_column);
+                    line.push(if edge { '|' } else { ' ' });
+                    if box_column == box_columns {
+                        break;
+                    }
+
+                    line.push(' ');
+                    for column in 0..box_width {
+                        let location = (location_row, box_column * box_width + column);
+                        match self.placements(location).next() {
+                            Some((grid, local)) => {
+                                line.push_str(&format!("{} ", self.grids[grid].get(local)))
+                            }
+                            None => line.push_str("  "),
+                        }
+                    }
+                }
+                lines.push(line);
+            }
+        }
+
+        let mut letters = " ".repeat(label_width + 1);
+        for box_column in 0..box_columns {
+            letters.push_str("  ");
+            for column in 0..box_width {
+                letters.push((b'a' + (box_column * box_width + column) as u8) as char);
+                letters.push(' ');
+            }
+        }
+        lines.push(letters);
+
+        for line in lines {
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn shared_squares_carry_propagation() {
+        let mut samurai = MultiBoard::samurai();
+        assert_eq!((21, 21), (samurai.height(), samurai.width()));
+        assert!(!samurai.contains((10, 0)));
+        assert!(!samurai.try_collapse(Number::One, (10, 0)));
+
+        // The top left corner of the middle board is shared with the top left board, so a
+        // number there rules out the rest of the top left board's row as well.
+        assert!(samurai.try_collapse(Number::Five, (14, 6)));
+        assert!(!samurai.candidates((14, 0)).contains(&Number::Five));
+        assert!(!samurai.candidates((8, 6)).contains(&Number::Five));
+        assert!(samurai.candidates((8, 0)).contains(&Number::Five));
+
+        assert!(samurai.undo((14, 6)));
+        assert!(samurai.candidates((14, 0)).contains(&Number::Five));
+        assert!(!samurai.undo((14, 6)));
+    }
+
+    #[test]
+    fn overlapping_puzzles_solve() {
+        let twodoku = MultiBoard::twodoku();
+        let solution = twodoku.solve().unwrap();
+        assert!(solution.is_solved());
+        assert!(solution.is_valid());
+        for row in 6..9 {
+            for column in 6..9 {
+                assert_eq!(
+                    solution.grids()[0].number((row - 6, column)),
+                    solution.grids()[1].number((row, column - 6))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn givens_and_constraints_reach_shared_squares() {
+        let mut twodoku = MultiBoard::twodoku();
+        assert!(!twodoku.add_constraint(2, Constraint::AntiKnight));
+
+        // The bottom row of the shared box is row 1 of the first board and row 7 of the second.
+        assert!(twodoku.add_constraint(
+            0,
+            Constraint::Cage {
+                sum: 3,
+                locations: vec![(0, 6), (0, 7)],
+            }
+        ));
+        assert_eq!(
+            vec![Number::One, Number::Two],
+            twodoku.grids()[1].candidates((6, 0))
+        );
+
+        assert!(twodoku.try_give(Number::One, (6, 7)));
+        assert!(twodoku.grids()[0].is_given((0, 7)));
+        assert!(twodoku.grids()[1].is_given((6, 1)));
+        assert_eq!(vec![Number::Two], twodoku.grids()[1].candidates((6, 0)));
+        assert!(!twodoku.undo((6, 7)));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{Board, Constraint};
+
+use std::fmt::Write;
+
+impl Board {
+    /// Draws the board with the outline of every box and cage, writing each cage's sum into the
+    /// border above its top left square. Box borders are drawn with solid lines and the remaining
+    /// cage borders with dotted ones. Unlike the plain grid, this works for boxes of any shape.
+    pub(crate) fn fmt_outlined(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let size = self.size();
+        let label_width = size.to_string().len();
+
+        let mut cage_ids = vec![vec![None; size]; size];
+        let mut sums = vec![vec![None; size]; size];
+        let cages = self
+            .constraints
+            .iter()
+            .filter_map(|constraint| match constraint {
+                Constraint::Cage { sum, locations } => Some((sum, locations)),
+                _ => None,
+            });
+        for (id, (sum, locations)) in cages.enumerate() {
+            for &(row, column) in locations {
+                cage_ids[row][column] = Some(id);
+            }
+            if let Some(&(row, column)) = locations
+                .iter()
+                .max_by_key(|(row, column)| (*row, size - column))
+            {
+                sums[row][column] = Some(*sum);
+            }
+        }
+
+        let cage_of = |row: Option<usize>, column: Option<usize>| match (row, column) {
+            (Some(row), Some(column)) if row < size && column < size => Some(cage_ids[row][column]),
+            _ => None,
+        };
+        let box_of = |row: Option<usize>, column: Option<usize>| match (row, column) {
+            (Some(row), Some(column)) if row < size && column < size => {
+                Some(self.find_box((row, column)))
+            }
+            _ => None,
+        };
+        // Whether the border between two squares (or a square and the edge of the board) is a box
+        // border, a cage border or no border at all.
+        let border = |first: (Option<usize>, Option<usize>),
+                      second: (Option<usize>, Option<usize>)| {
+            if box_of(first.0, first.1) != box_of(second.0, second.1) {
+                Some(true)
+            } else if cage_of(first.0, first.1) != cage_of(second.0, second.1) {
+                Some(false)
+            } else {
+                None
+            }
+        };
+
+        let mut lines = Vec::with_capacity(size * 2 + 2);
+        lines.push(String::new());
+
+        // Rows are drawn from the last down to the first, each preceded by the border above it.
+        for display_row in 0..=size {
+            let above = (display_row > 0).then(|| size - display_row);
+            let below = size.checked_sub(display_row + 1);
+
+            let mut line = " ".repeat(label_width + 1);
+            for column in 0..=size {
+                let left = column.checked_sub(1);
+                let right = (column < size).then_some(column);
+
+                let edges = [
+                    border((above, left), (above, right)),
+                    border((below, left), (below, right)),
+                    border((above, left), (below, left)),
+                    border((above, right), (below, right)),
+                ];
+                line.push(if edges.contains(&Some(true)) {
+                    '+'
+                } else if edges.contains(&Some(false)) {
+                    '.'
+                } else {
+                    ' '
+                });
+
+                if let Some(column) = right {
+                    let fill = match border((above, right), (below, right)) {
+                        Some(true) => '-',
+                        Some(false) => '.',
+                        None => ' ',
+                    };
+                    match below.and_then(|row| sums[row][column]) {
+                        Some(sum) => {
+                            let sum = sum.to_string();
+                            line.push_str(&sum);
+                            line.push_str(
+                                &fill.to_string().repeat(3usize.saturating_sub(sum.len())),
+                            );
+                        }
+                        None => line.push_str(&fill.to_string().repeat(3)),
+                    }
+                }
+            }
+            lines.push(line);
+
+            let Some(row) = below else {
+                break;
+            };
+            let mut line = format!("{:>label_width$} ", row + 1);
+            for column in 0..=size {
+                let left = column.checked_sub(1);
+                let right = (column < size).then_some(column);
+
+                line.push(match border((Some(row), left), (Some(row), right)) {
+                    Some(true) => '|',
+                    Some(false) => ':',
+                    None => ' ',
+                });
+                if let Some(column) = right {
+                    write!(
+                        line,
+                        " {}{}",
+                        self.get((row, column)),
+                        self.marker((row, column))
+                    )?;
+                }
+            }
+            lines.push(line);
+        }
+
+        let mut letters = " ".repeat(label_width + 1);
+        for column in 0..size {
+            write!(letters, "  {} ", (b'a' + column as u8) as char)?;
+        }
+        lines.push(letters);
+
+        for line in lines {
+            writeln!(f, "{}", line.trim_end())?;
+        }
+
+        Ok(())
+    }
+}
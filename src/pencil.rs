@@ -0,0 +1,253 @@
+use crate::{Board, Number};
+
+/// Where in a square a pencil mark is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// In a corner of the square, usually for a number that has to go in one of a few squares
+    /// of a box.
+    Corner,
+    /// In the middle of the square, usually for the numbers the square could still hold.
+    Center,
+}
+
+/// The notes a player pencils into a board's squares, kept apart from the candidates the board
+/// keeps track of itself. Marks only change when the player changes them, so placing a number
+/// on the board never rubs any out.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "crate::schema::PencilMarksData")
+)]
+pub struct PencilMarks {
+    size: usize,
+    corner: Vec<Vec<Vec<Number>>>,
+    center: Vec<Vec<Vec<Number>>>,
+}
+impl PencilMarks {
+    /// No marks at all, for a board `size` squares across.
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            corner: vec![vec![Vec::new(); size]; size],
+            center: vec![vec![Vec::new(); size]; size],
+        }
+    }
+
+    /// No marks at all, for a board the size of `board`.
+    pub fn for_board(board: &Board) -> Self {
+        Self::new(board.size())
+    }
+
+    /// The number of rows and columns of the board the marks are for.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The marks of a kind pencilled into a square, from lowest to highest.
+    pub fn marks(&self, mark: Mark, location: (usize, usize)) -> &[Number] {
+        let marks = match mark {
+            Mark::Corner => &self.corner,
+            Mark::Center => &self.center,
+        };
+
+        &marks[location.0][location.1]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.corner
+            .iter()
+            .chain(&self.center)
+            .flatten()
+            .all(Vec::is_empty)
+    }
+
+    /// Pencils `number` into a square. Returns false if it is already there, or is too big for
+    /// the board.
+    pub fn add(&mut self, mark: Mark, number: Number, location: (usize, usize)) -> bool {
+        if number.index() >= self.size {
+            return false;
+        }
+
+        let marks = self.marks_mut(mark, location);
+        match marks.binary_search(&number) {
+            Ok(_) => false,
+            Err(index) => {
+                marks.insert(index, number);
+
+                true
+            }
+        }
+    }
+
+    /// Rubs `number` out of a square. Returns false if it wasn't there.
+    pub fn remove(&mut self, mark: Mark, number: Number, location: (usize, usize)) -> bool {
+        let marks = self.marks_mut(mark, location);
+        match marks.binary_search(&number) {
+            Ok(index) => {
+                marks.remove(index);
+
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Rubs `number` out of a square if it is there, and pencils it in otherwise. Returns whether
+    /// it is there afterwards.
+    pub fn toggle(&mut self, mark: Mark, number: Number, location: (usize, usize)) -> bool {
+        !self.remove(mark, number, location) && self.add(mark, number, location)
+    }
+
+    /// Rubs out every mark in a square.
+    pub fn clear(&mut self, location: (usize, usize)) {
+        self.corner[location.0][location.1].clear();
+        self.center[location.0][location.1].clear();
+    }
+
+    /// Replaces the marks of a kind in every unsolved square with the candidates the board has
+    /// left for it, and rubs them out of solved squares. Marks of the other kind are kept.
+    ///
+    /// # Panics
+    ///
+    /// If the board is a different size from the one the marks are for.
+    pub fn fill_from_candidates(&mut self, mark: Mark, board: &Board) {
+        assert_eq!(
+            self.size,
+            board.size(),
+            "Pencil marks must be for a board of the same size"
+        );
+        for row in 0..self.size {
+            for column in 0..self.size {
+                let location = (row, column);
+                *self.marks_mut(mark, location) = match board.number(location) {
+                    Some(_) => Vec::new(),
+                    None => board.candidates(location),
+                };
+            }
+        }
+    }
+
+    /// The marks in a square as they are written by [`BoardFormatter::pencil_marks`]: the
+    /// corner marks followed by a `/` and the center marks, or a `.` if there are none.
+    ///
+    /// [`BoardFormatter::pencil_marks`]: crate::BoardFormatter::pencil_marks
+    pub(crate) fn written(&self, location: (usize, usize)) -> String {
+        let write = |marks: &[Number]| marks.iter().map(|number| number.to_char()).collect();
+        let corner: String = write(self.marks(Mark::Corner, location));
+        let center: String = write(self.marks(Mark::Center, location));
+        match (corner.is_empty(), center.is_empty()) {
+            (true, true) => ".".to_string(),
+            (false, true) => corner,
+            _ => format!("{corner}/{center}"),
+        }
+    }
+
+    fn marks_mut(&mut self, mark: Mark, location: (usize, usize)) -> &mut Vec<Number> {
+        let marks = match mark {
+            Mark::Corner => &mut self.corner,
+            Mark::Center => &mut self.center,
+        };
+
+        &mut marks[location.0][location.1]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Labels, Mark, Number, PencilMarks};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn marks_are_added_removed_and_toggled() {
+        let mut marks = PencilMarks::new(4);
+        assert!(marks.is_empty());
+
+        assert!(marks.add(Mark::Corner, Number::Three, (0, 0)));
+        assert!(marks.add(Mark::Corner, Number::One, (0, 0)));
+        assert!(!marks.add(Mark::Corner, Number::One, (0, 0)));
+        assert!(!marks.add(Mark::Corner, Number::Five, (0, 0)));
+        assert_eq!(
+            [Number::One, Number::Three],
+            marks.marks(Mark::Corner, (0, 0))
+        );
+        assert!(marks.marks(Mark::Center, (0, 0)).is_empty());
+
+        assert!(marks.remove(Mark::Corner, Number::One, (0, 0)));
+        assert!(!marks.remove(Mark::Corner, Number::One, (0, 0)));
+        assert!(marks.toggle(Mark::Center, Number::Two, (0, 0)));
+        assert!(!marks.toggle(Mark::Center, Number::Two, (0, 0)));
+        assert!(!marks.toggle(Mark::Center, Number::Five, (0, 0)));
+        assert_eq!([Number::Three], marks.marks(Mark::Corner, (0, 0)));
+
+        marks.clear((0, 0));
+        assert!(marks.is_empty());
+    }
+
+    #[test]
+    fn marks_are_kept_apart_from_candidates() {
+        let mut board = Board::new(2, 2).unwrap();
+        let mut marks = PencilMarks::new(4);
+        assert!(marks.add(Mark::Corner, Number::One, (0, 1)));
+        assert!(marks.add(Mark::Center, Number::Four, (3, 3)));
+
+        // Placing a number leaves the marks alone, even where it rules them out.
+        assert!(board.try_collapse(Number::One, (0, 0)));
+        assert_eq!([Number::One], marks.marks(Mark::Corner, (0, 1)));
+
+        marks.fill_from_candidates(Mark::Center, &board);
+        assert!(marks.marks(Mark::Center, (0, 0)).is_empty());
+        assert_eq!(board.candidates((0, 1)), marks.marks(Mark::Center, (0, 1)));
+        assert_eq!(board.candidates((3, 3)), marks.marks(Mark::Center, (3, 3)));
+        assert_eq!([Number::One], marks.marks(Mark::Corner, (0, 1)));
+
+        let mut marks = PencilMarks::for_board(&board);
+        assert!(marks.add(Mark::Corner, Number::Two, (3, 0)));
+        assert!(marks.add(Mark::Corner, Number::Three, (3, 0)));
+        assert!(marks.add(Mark::Center, Number::Four, (3, 1)));
+        assert!(marks.add(Mark::Corner, Number::One, (3, 2)));
+        assert!(marks.add(Mark::Center, Number::Three, (3, 2)));
+        assert_eq!(
+            [
+                "  |---------|---------|\n",
+                "4 | 23  /4  | 1/3 .   |\n",
+                "3 | .   .   | .   .   |\n",
+                "  |---------|---------|\n",
+                "2 | .   .   | .   .   |\n",
+                "1 | 1   .   | .   .   |\n",
+                "  |---------|---------|\n",
+                "    a   b     c   d\n",
+            ]
+            .concat(),
+            board.formatter().pencil_marks(&marks).to_string()
+        );
+        // The marks follow the formatter's other options.
+        assert_eq!(
+            [
+                "     C1  C2    C3  C4\n",
+                "   |---------|---------|\n",
+                "R1 | 1   .   | .   .   |\n",
+                "R2 | .   .   | .   .   |\n",
+                "   |---------|---------|\n",
+                "R3 | .   .   | .   .   |\n",
+                "R4 | 23  /4  | 1/3 .   |\n",
+                "   |---------|---------|\n",
+            ]
+            .concat(),
+            board
+                .formatter()
+                .pencil_marks(&marks)
+                .row_one_at_top(true)
+                .labels(Labels::RowColumn)
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "same size")]
+    fn marks_must_fit_their_board() {
+        let marks = PencilMarks::new(4);
+        let _ = Board::default().formatter().pencil_marks(&marks);
+    }
+}
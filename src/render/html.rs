@@ -0,0 +1,317 @@
+use super::{
+    svg::{self, SCALE},
+    Theme,
+};
+use crate::{Board, Number};
+
+/// The player, which draws what is entered over the picture of the puzzle. It reads the puzzle
+/// from `puzzle`, which the page defines before it.
+const PLAYER: &str = r#""use strict";
+(() => {
+  const ns = "http://www.w3.org/2000/svg";
+  const { size, boxHeight, boxWidth, scale, margin, symbols, givens, solutions, solution } = puzzle;
+  const picture = document.querySelector("svg");
+  const highlights = document.getElementById("highlights");
+  const marks = document.getElementById("marks");
+  const status = document.getElementById("status");
+  const pencilButton = document.getElementById("pencil");
+
+  const entries = Array.from(puzzle.entries, (c) => (c === "." ? "" : c));
+  const pencilMarks = Array.from(entries, () => new Set());
+  let wrong = new Set();
+  let selected = Math.max(0, givens.indexOf("."));
+  let pencilling = false;
+
+  function shape(parent, name, attributes, text) {
+    const element = document.createElementNS(ns, name);
+    for (const [key, value] of Object.entries(attributes)) {
+      element.setAttribute(key, value);
+    }
+    if (text !== undefined) {
+      element.textContent = text;
+    }
+    parent.appendChild(element);
+  }
+
+  function square(cell, role) {
+    const x = (margin + (cell % size)) * scale;
+    const y = (margin + Math.floor(cell / size)) * scale;
+    shape(highlights, "rect", { class: role, x, y, width: scale, height: scale });
+  }
+
+  function draw() {
+    highlights.replaceChildren();
+    marks.replaceChildren();
+    for (const cell of wrong) {
+      square(cell, "conflict");
+    }
+    square(selected, "hint");
+
+    const rows = boxHeight, columns = boxWidth;
+    entries.forEach((entry, cell) => {
+      const x = margin + (cell % size), y = margin + Math.floor(cell / size);
+      if (entry) {
+        shape(marks, "text", {
+          class: "entry", x: (x + 0.5) * scale, y: (y + 0.5) * scale, "font-size": 0.6 * scale,
+        }, entry);
+        return;
+      }
+      // Pencil marks sit where they would on a keypad shaped like a box.
+      for (const mark of pencilMarks[cell]) {
+        const index = symbols.indexOf(mark);
+        const i = Math.floor(index / columns), j = index % columns;
+        shape(marks, "text", {
+          class: "candidate",
+          x: (x + 0.1 + (0.8 * (j + 0.5)) / columns) * scale,
+          y: (y + 0.1 + (0.8 * (i + 0.5)) / rows) * scale,
+          "font-size": (0.7 / Math.max(rows, columns)) * scale,
+        }, mark);
+      }
+    });
+    pencilButton.textContent = pencilling ? "Pencil: on" : "Pencil: off";
+  }
+
+  function enter(symbol) {
+    if (givens[selected] !== ".") {
+      return;
+    }
+    if (symbol === "") {
+      entries[selected] = "";
+      pencilMarks[selected].clear();
+    } else if (pencilling) {
+      const marks = pencilMarks[selected];
+      marks.has(symbol) ? marks.delete(symbol) : marks.add(symbol);
+    } else {
+      entries[selected] = entries[selected] === symbol ? "" : symbol;
+    }
+    wrong.delete(selected);
+    status.textContent = "";
+    draw();
+  }
+
+  function check() {
+    // Entries are only checked against a unique solution, as any of several could be right.
+    if (!solution) {
+      status.textContent = solutions === 0
+        ? "This puzzle has no solution to check against."
+        : "This puzzle has more than one solution, so there is none to check against.";
+      return;
+    }
+    wrong = new Set();
+    let empty = 0;
+    entries.forEach((entry, cell) => {
+      if (!entry) {
+        empty += 1;
+      } else if (entry !== solution[cell]) {
+        wrong.add(cell);
+      }
+    });
+    if (wrong.size > 0) {
+      status.textContent = wrong.size === 1 ? "1 square is wrong." : `${wrong.size} squares are wrong.`;
+    } else if (empty > 0) {
+      status.textContent = "No mistakes so far.";
+    } else {
+      status.textContent = "Solved!";
+    }
+    draw();
+  }
+
+  picture.addEventListener("click", (event) => {
+    const bounds = picture.getBoundingClientRect();
+    const box = picture.viewBox.baseVal;
+    const x = ((event.clientX - bounds.left) / bounds.width) * box.width / scale - margin;
+    const y = ((event.clientY - bounds.top) / bounds.height) * box.height / scale - margin;
+    if (x >= 0 && y >= 0 && x < size && y < size) {
+      selected = Math.floor(y) * size + Math.floor(x);
+      draw();
+    }
+  });
+
+  document.addEventListener("keydown", (event) => {
+    if (event.ctrlKey || event.metaKey || event.altKey) {
+      return;
+    }
+    const moves = { ArrowUp: -size, ArrowDown: size, ArrowLeft: -1, ArrowRight: 1 };
+    const key = event.key.toUpperCase();
+    if (event.key in moves) {
+      const next = selected + moves[event.key];
+      const sameRow = Math.floor(next / size) === Math.floor(selected / size);
+      if (next >= 0 && next < size * size && (Math.abs(moves[event.key]) === size || sameRow)) {
+        selected = next;
+      }
+      draw();
+    } else if (event.key === " ") {
+      pencilling = !pencilling;
+      draw();
+    } else if (["BACKSPACE", "DELETE", "0", "."].includes(key)) {
+      enter("");
+    } else if (key.length === 1 && symbols.includes(key)) {
+      enter(key);
+    } else {
+      return;
+    }
+    event.preventDefault();
+  });
+
+  const keypad = document.getElementById("keypad");
+  for (const symbol of symbols) {
+    const button = document.createElement("button");
+    button.textContent = symbol;
+    button.addEventListener("click", () => enter(symbol));
+    keypad.appendChild(button);
+  }
+  document.getElementById("erase").addEventListener("click", () => enter(""));
+  pencilButton.addEventListener("click", () => {
+    pencilling = !pencilling;
+    draw();
+  });
+  document.getElementById("check").addEventListener("click", check);
+
+  draw();
+})();
+"#;
+
+const PAGE_STYLE: &str = "\
+body { font-family: sans-serif; max-width: 36em; margin: 1em auto; padding: 0 1em }
+svg { width: 100%; height: auto; cursor: pointer; user-select: none }
+#keypad, #controls { display: flex; flex-wrap: wrap; gap: 0.3em; margin-top: 0.5em }
+button { font-size: 1.1em; min-width: 2.4em; padding: 0.3em }
+";
+
+impl Board {
+    /// Writes a web page for playing the puzzle offline, with `title` as its heading. Squares
+    /// are picked by clicking them or with the arrow keys, and numbers entered with the keyboard
+    /// or the buttons under the board. The space bar or the Pencil button switches to pencil
+    /// marks, and Check marks every number that doesn't match the puzzle's solution, or says
+    /// there is nothing to check against if the puzzle has no solution or more than one. Numbers
+    /// already placed on the board start out entered, but can be changed. The page needs nothing
+    /// else to work, not even a network.
+    pub fn to_html(&self, title: &str) -> String {
+        let size = self.size();
+        let mut puzzle = self.clone();
+        let mut givens = String::new();
+        let mut entries = String::new();
+        for row in (0..size).rev() {
+            for column in 0..size {
+                let location = (row, column);
+                match self.number(location) {
+                    Some(number) if self.is_given(location) => {
+                        givens.push(number.to_char());
+                        entries.push('.');
+                    }
+                    Some(number) => {
+                        puzzle.undo(location);
+                        givens.push('.');
+                        entries.push(number.to_char());
+                    }
+                    None => {
+                        givens.push('.');
+                        entries.push('.');
+                    }
+                }
+            }
+        }
+        let solutions = puzzle.count_solutions(2);
+        let solution: String = match puzzle.solve().filter(|_| solutions == 1) {
+            Some(solution) => (0..size)
+                .rev()
+                .flat_map(|row| (0..size).map(move |column| (row, column)))
+                .filter_map(|location| solution.number(location))
+                .map(Number::to_char)
+                .collect(),
+            None => String::new(),
+        };
+        let symbols: String = (0..size)
+            .filter_map(Number::from_index)
+            .map(Number::to_char)
+            .collect();
+
+        let layout = puzzle.layout(&[], false);
+        let margin = (layout.width - size as f64) / 2.0;
+        let mut picture = svg::header(&layout, &Theme::light());
+        // The squares entered into are highlighted over the background and under everything
+        // else, and what is entered goes on top.
+        let (background, shapes) = layout.shapes.split_at(1);
+        for shape in background {
+            picture.push_str(&svg::element(shape));
+        }
+        picture.push_str("<g id=\"highlights\"></g>\n");
+        for shape in shapes {
+            picture.push_str(&svg::element(shape));
+        }
+        picture.push_str("<g id=\"marks\"></g>\n</svg>\n");
+
+        let title = escape(title);
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n<style>\n{PAGE_STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n{picture}<div id=\"keypad\"></div>\n<div id=\"controls\">\n\
+             <button id=\"erase\">Erase</button>\n<button id=\"pencil\">Pencil: off</button>\n\
+             <button id=\"check\">Check</button>\n</div>\n<p id=\"status\"></p>\n<script>\n\
+             const puzzle = {{\"size\": {size}, \"boxHeight\": {}, \"boxWidth\": {}, \
+             \"scale\": {SCALE}, \"margin\": {margin}, \"symbols\": \"{symbols}\", \"givens\": \"{givens}\", \
+             \"entries\": \"{entries}\", \"solutions\": {solutions}, \"solution\": \"{solution}\"}};\n{PLAYER}</script>\n\
+             </body>\n</html>\n",
+            self.box_height, self.box_width,
+        )
+    }
+}
+
+/// Escapes the characters HTML gives a meaning to.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn html_pages_hold_the_puzzle_and_its_solution() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+        assert!(board.try_give(Number::Two, (3, 1)));
+        assert!(board.try_collapse(Number::Three, (3, 2)));
+
+        let html = board.to_html("Tom & Jerry's <puzzle>");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Tom &amp; Jerry's &lt;puzzle&gt;</title>"));
+        assert!(html.contains("\"symbols\": \"1234\""));
+        assert!(html.contains(&format!("\"givens\": \"12{}\"", ".".repeat(14))));
+        // Numbers that aren't givens can be changed, so they're drawn by the player.
+        assert!(html.contains(&format!("\"entries\": \"..3{}\"", ".".repeat(13))));
+        assert!(!html.contains("class=\"entry\""));
+        // The solution is found from the givens alone, which two givens don't settle.
+        assert!(html.contains("\"solutions\": 2, \"solution\": \"\""));
+
+        // Everything is in the page itself.
+        assert!(!html.contains("src="));
+        assert!(!html.contains("href="));
+    }
+
+    #[test]
+    fn html_pages_only_check_against_a_unique_solution() {
+        let solution = [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]];
+        let mut board = Board::new(2, 2).unwrap();
+        for (row, values) in solution.iter().enumerate() {
+            for (column, &value) in values.iter().enumerate() {
+                if row != column {
+                    assert!(board.try_give(Number::ALL[value - 1], (row, column)));
+                }
+            }
+        }
+
+        let html = board.to_html("Unique");
+        assert!(html.contains("\"solutions\": 1, "));
+        // The solution is written from the top row down, as the board is displayed.
+        let written = &html[html.find("\"solution\": \"").unwrap() + 13..];
+        assert_eq!(Some(16), written.find('"'));
+        assert_eq!("4321214334121234", &written[..16]);
+    }
+}
@@ -0,0 +1,514 @@
+mod html;
+mod pdf;
+mod png;
+mod svg;
+mod theme;
+mod tikz;
+
+pub use theme::Theme;
+
+use crate::{Board, Constraint, Dot};
+
+/// A way of drawing attention to a square when a board is drawn as a picture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Highlight {
+    /// A square worth looking at next, such as one a hint is about.
+    Hint,
+    /// A square whose number breaks a rule.
+    Conflict,
+}
+
+/// What a shape is part of, which decides how each kind of picture colors it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Role {
+    Background,
+    Hint,
+    Conflict,
+    /// Shaded squares and the bodies of thermometers.
+    Shade,
+    /// Diagonals, cages, arrows and the other marks of variant rules.
+    Decoration,
+    /// The thin lines between squares.
+    Grid,
+    /// The thick lines around boxes and the board.
+    Border,
+    Given,
+    Entry,
+    Candidate,
+    /// Clues written outside the board.
+    Clue,
+}
+
+/// Something to draw, measured in squares from the top left corner of the picture.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Shape {
+    Rect {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        role: Role,
+    },
+    /// Straight lines joining the points in order.
+    Line {
+        points: Vec<(f64, f64)>,
+        width: f64,
+        dashed: bool,
+        role: Role,
+    },
+    /// A circle filled with its role's color, or filled with the background and outlined with
+    /// its role's color if it isn't `filled`.
+    Circle {
+        center: (f64, f64),
+        radius: f64,
+        filled: bool,
+        role: Role,
+    },
+    /// Text centered on a point, `size` squares tall.
+    Text {
+        center: (f64, f64),
+        size: f64,
+        text: String,
+        role: Role,
+    },
+}
+
+/// A board laid out as shapes, from the bottom layer to the top, ready to be drawn by any kind
+/// of picture.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Layout {
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+    pub(crate) shapes: Vec<Shape>,
+}
+
+/// How thick lines are, in squares.
+const GRID_WIDTH: f64 = 0.02;
+const BORDER_WIDTH: f64 = 0.06;
+const DECORATION_WIDTH: f64 = 0.04;
+
+impl Board {
+    /// Lays the board out as shapes: the squares with their highlights, the marks of its variant
+    /// rules, the grid with boxes outlined, and the numbers, with candidates pencilled into
+    /// unsolved squares if `candidates` is set. Outside clues get a margin of one square around
+    /// the board.
+    pub(crate) fn layout(
+        &self,
+        highlights: &[((usize, usize), Highlight)],
+        candidates: bool,
+    ) -> Layout {
+        let size = self.size();
+        let margin = if self
+            .constraints
+            .iter()
+            .any(|constraint| constraint.outside_clue().is_some())
+        {
+            1.0
+        } else {
+            0.25
+        };
+        let side = size as f64 + 2.0 * margin;
+        let corner = |location: (usize, usize)| {
+            (
+                margin + location.1 as f64,
+                margin + (size - 1 - location.0) as f64,
+            )
+        };
+        let center = |location: (usize, usize)| {
+            let (x, y) = corner(location);
+            (x + 0.5, y + 0.5)
+        };
+        let square = |location: (usize, usize), inset: f64, role: Role| {
+            let (x, y) = corner(location);
+            Shape::Rect {
+                x: x + inset,
+                y: y + inset,
+                width: 1.0 - 2.0 * inset,
+                height: 1.0 - 2.0 * inset,
+                role,
+            }
+        };
+
+        let mut shapes = vec![Shape::Rect {
+            x: 0.0,
+            y: 0.0,
+            width: side,
+            height: side,
+            role: Role::Background,
+        }];
+        for &(location, highlight) in highlights {
+            if location.0 < size && location.1 < size {
+                let role = match highlight {
+                    Highlight::Hint => Role::Hint,
+                    Highlight::Conflict => Role::Conflict,
+                };
+                shapes.push(square(location, 0.0, role));
+            }
+        }
+
+        for constraint in &self.constraints {
+            match constraint {
+                Constraint::Window(locations) | Constraint::Region(locations) => {
+                    shapes.extend(
+                        locations
+                            .iter()
+                            .map(|&location| square(location, 0.0, Role::Shade)),
+                    );
+                }
+                Constraint::Even(locations) => {
+                    shapes.extend(
+                        locations
+                            .iter()
+                            .map(|&location| square(location, 0.15, Role::Shade)),
+                    );
+                }
+                Constraint::Odd(locations) => {
+                    shapes.extend(locations.iter().map(|&location| Shape::Circle {
+                        center: center(location),
+                        radius: 0.35,
+                        filled: true,
+                        role: Role::Shade,
+                    }));
+                }
+                Constraint::Thermometer(locations) => {
+                    if let Some(&bulb) = locations.first() {
+                        shapes.push(Shape::Circle {
+                            center: center(bulb),
+                            radius: 0.35,
+                            filled: true,
+                            role: Role::Shade,
+                        });
+                    }
+                    shapes.push(Shape::Line {
+                        points: locations.iter().map(|&location| center(location)).collect(),
+                        width: 0.25,
+                        dashed: false,
+                        role: Role::Shade,
+                    });
+                }
+                Constraint::Palindrome(locations) => shapes.push(Shape::Line {
+                    points: locations.iter().map(|&location| center(location)).collect(),
+                    width: 0.15,
+                    dashed: false,
+                    role: Role::Shade,
+                }),
+                Constraint::Diagonal(locations) => {
+                    if let (Some(&first), Some(&last)) = (locations.first(), locations.last()) {
+                        let (from, to) = (center(first), center(last));
+                        let step = (
+                            (to.0 - from.0).signum() * 0.5,
+                            (to.1 - from.1).signum() * 0.5,
+                        );
+                        shapes.push(Shape::Line {
+                            points: vec![
+                                (from.0 - step.0, from.1 - step.1),
+                                (to.0 + step.0, to.1 + step.1),
+                            ],
+                            width: DECORATION_WIDTH,
+                            dashed: false,
+                            role: Role::Decoration,
+                        });
+                    }
+                }
+                Constraint::Arrow { circle, line } => {
+                    shapes.push(Shape::Circle {
+                        center: center(*circle),
+                        radius: 0.4,
+                        filled: false,
+                        role: Role::Decoration,
+                    });
+                    shapes.extend(arrow(
+                        center(*circle),
+                        line.iter().map(|&location| center(location)).collect(),
+                    ));
+                }
+                Constraint::Cage { sum, locations } => {
+                    shapes.extend(cage_outline(locations, corner));
+                    if let Some(&first) = locations
+                        .iter()
+                        .max_by_key(|(row, column)| (*row, size - column))
+                    {
+                        let (x, y) = corner(first);
+                        shapes.push(Shape::Text {
+                            center: (x + 0.2, y + 0.2),
+                            size: 0.22,
+                            text: sum.to_string(),
+                            role: Role::Decoration,
+                        });
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        // Each edge between two squares is drawn thin, or thick if it separates two boxes.
+        for row in 0..size {
+            for column in 0..size {
+                let (x, y) = corner((row, column));
+                let right = (column + 1 < size).then_some((row, column + 1));
+                let below = row.checked_sub(1).map(|row| (row, column));
+                for (neighbor, points) in [
+                    (right, vec![(x + 1.0, y), (x + 1.0, y + 1.0)]),
+                    (below, vec![(x, y + 1.0), (x + 1.0, y + 1.0)]),
+                ] {
+                    let Some(neighbor) = neighbor else {
+                        continue;
+                    };
+                    let thick = self.find_box((row, column)) != self.find_box(neighbor);
+                    shapes.push(Shape::Line {
+                        points,
+                        width: if thick { BORDER_WIDTH } else { GRID_WIDTH },
+                        dashed: false,
+                        role: if thick { Role::Border } else { Role::Grid },
+                    });
+                }
+            }
+        }
+        let far = margin + size as f64;
+        shapes.push(Shape::Line {
+            points: vec![
+                (margin, margin),
+                (far, margin),
+                (far, far),
+                (margin, far),
+                (margin, margin),
+            ],
+            width: BORDER_WIDTH,
+            dashed: false,
+            role: Role::Border,
+        });
+
+        for constraint in &self.constraints {
+            match constraint {
+                Constraint::Dot { dot, locations } => {
+                    let (first, second) = (center(locations[0]), center(locations[1]));
+                    let middle = ((first.0 + second.0) / 2.0, (first.1 + second.1) / 2.0);
+                    shapes.extend(dot_shapes(*dot, middle));
+                }
+                Constraint::GreaterThan { greater, lesser } => {
+                    shapes.push(chevron(center(*greater), center(*lesser)));
+                }
+                _ => (),
+            }
+            if let Some(((row, column), text)) = constraint.outside_clue() {
+                shapes.push(Shape::Text {
+                    center: (
+                        margin + column as f64 + 0.5,
+                        margin + (size as isize - 1 - row) as f64 + 0.5,
+                    ),
+                    size: 0.45,
+                    text,
+                    role: Role::Clue,
+                });
+            }
+        }
+
+        for row in 0..size {
+            for column in 0..size {
+                let location = (row, column);
+                let (x, y) = corner(location);
+                if let Some(number) = self.number(location) {
+                    shapes.push(Shape::Text {
+                        center: center(location),
+                        size: 0.6,
+                        text: number.to_string(),
+                        role: if self.is_given(location) {
+                            Role::Given
+                        } else {
+                            Role::Entry
+                        },
+                    });
+                    continue;
+                }
+                if !candidates {
+                    continue;
+                }
+
+                // Candidates sit where they would on a keypad shaped like a box.
+                let (rows, columns) = (self.box_height as f64, self.box_width as f64);
+                for number in self.candidates(location) {
+                    let (i, j) = (
+                        number.index() / self.box_width,
+                        number.index() % self.box_width,
+                    );
+                    shapes.push(Shape::Text {
+                        center: (
+                            x + 0.1 + 0.8 * (j as f64 + 0.5) / columns,
+                            y + 0.1 + 0.8 * (i as f64 + 0.5) / rows,
+                        ),
+                        size: 0.7 / rows.max(columns),
+                        text: number.to_string(),
+                        role: Role::Candidate,
+                    });
+                }
+            }
+        }
+
+        Layout {
+            width: side,
+            height: side,
+            shapes,
+        }
+    }
+}
+
+/// The dashed outline just inside the edge of a cage. Each side of a square that borders
+/// something outside the cage gets a line, which runs on into the next square along the cage's
+/// edge, or stops short or goes past the corner where the edge turns.
+fn cage_outline(
+    locations: &[(usize, usize)],
+    corner: impl Fn((usize, usize)) -> (f64, f64),
+) -> Vec<Shape> {
+    const INSET: f64 = 0.08;
+    let contains = |row: isize, column: isize| {
+        row >= 0 && column >= 0 && locations.contains(&(row as usize, column as usize))
+    };
+    // How far the line along one side reaches past the corner of its square, towards a
+    // neighbor `along` it, when `outward` is the direction the side faces.
+    let reach = |row: isize, column: isize, along: (isize, isize), outward: (isize, isize)| {
+        if !contains(row + along.0, column + along.1) {
+            -INSET
+        } else if contains(row + along.0 + outward.0, column + along.1 + outward.1) {
+            INSET
+        } else {
+            0.0
+        }
+    };
+
+    let mut shapes = Vec::new();
+    for &location in locations {
+        let (row, column) = (location.0 as isize, location.1 as isize);
+        let (x, y) = corner(location);
+        // Rows count up the board, so "up" is a step of +1 in row and -1 in y.
+        let sides = [
+            ((1, 0), (x, y + INSET), (x + 1.0, y + INSET), (0, -1)),
+            (
+                (-1, 0),
+                (x, y + 1.0 - INSET),
+                (x + 1.0, y + 1.0 - INSET),
+                (0, -1),
+            ),
+            ((0, -1), (x + INSET, y + 1.0), (x + INSET, y), (-1, 0)),
+            (
+                (0, 1),
+                (x + 1.0 - INSET, y + 1.0),
+                (x + 1.0 - INSET, y),
+                (-1, 0),
+            ),
+        ];
+        for (outward, start, end, backward) in sides {
+            if contains(row + outward.0, column + outward.1) {
+                continue;
+            }
+
+            // Lines run left to right or bottom to top on screen, which is `backward` in the
+            // board's directions and the opposite forwards.
+            let forward = (-backward.0, -backward.1);
+            let before = reach(row, column, backward, outward);
+            let after = reach(row, column, forward, outward);
+            let (start, end) = if start.1 == end.1 {
+                ((start.0 - before, start.1), (end.0 + after, end.1))
+            } else {
+                ((start.0, start.1 + before), (end.0, end.1 - after))
+            };
+            shapes.push(Shape::Line {
+                points: vec![start, end],
+                width: GRID_WIDTH,
+                dashed: true,
+                role: Role::Decoration,
+            });
+        }
+    }
+
+    shapes
+}
+
+/// The line of an arrow from the edge of its circle through each square, with a head at the end.
+fn arrow(circle: (f64, f64), line: Vec<(f64, f64)>) -> Vec<Shape> {
+    let Some(&first) = line.first() else {
+        return Vec::new();
+    };
+    let direction = |from: (f64, f64), to: (f64, f64)| {
+        let length = (to.0 - from.0).hypot(to.1 - from.1).max(f64::EPSILON);
+        ((to.0 - from.0) / length, (to.1 - from.1) / length)
+    };
+
+    let out = direction(circle, first);
+    let mut points = vec![(circle.0 + out.0 * 0.4, circle.1 + out.1 * 0.4)];
+    points.extend(&line);
+
+    let tip = line[line.len() - 1];
+    let before = points[points.len() - 2];
+    let back = direction(tip, before);
+    let head = |sign: f64| {
+        (
+            tip.0 + (back.0 - sign * back.1) * 0.2,
+            tip.1 + (back.1 + sign * back.0) * 0.2,
+        )
+    };
+
+    [points, vec![head(1.0), tip, head(-1.0)]]
+        .into_iter()
+        .map(|points| Shape::Line {
+            points,
+            width: DECORATION_WIDTH,
+            dashed: false,
+            role: Role::Decoration,
+        })
+        .collect()
+}
+
+/// The shapes for a dot on the edge between two squares, centered on `middle`.
+fn dot_shapes(dot: Dot, middle: (f64, f64)) -> Vec<Shape> {
+    let circle = |filled: bool, role: Role| Shape::Circle {
+        center: middle,
+        radius: 0.12,
+        filled,
+        role,
+    };
+
+    match dot {
+        Dot::White => vec![circle(false, Role::Decoration)],
+        Dot::Black => vec![circle(true, Role::Decoration)],
+        Dot::X | Dot::V => vec![
+            circle(true, Role::Background),
+            Shape::Text {
+                center: middle,
+                size: 0.25,
+                text: if dot == Dot::X { "X" } else { "V" }.to_string(),
+                role: Role::Decoration,
+            },
+        ],
+    }
+}
+
+/// A `>` on the edge between two squares, opening towards the greater one.
+fn chevron(greater: (f64, f64), lesser: (f64, f64)) -> Shape {
+    let middle = ((greater.0 + lesser.0) / 2.0, (greater.1 + lesser.1) / 2.0);
+    let along = (lesser.0 - greater.0, lesser.1 - greater.1);
+    let across = (-along.1, along.0);
+    let point = |forward: f64, sideways: f64| {
+        (
+            middle.0 + along.0 * forward + across.0 * sideways,
+            middle.1 + along.1 * forward + across.1 * sideways,
+        )
+    };
+
+    Shape::Line {
+        points: vec![point(-0.08, 0.12), point(0.08, 0.0), point(-0.08, -0.12)],
+        width: DECORATION_WIDTH,
+        dashed: false,
+        role: Role::Decoration,
+    }
+}
+
+/// Writes a coordinate with at most two decimal places and no trailing zeros.
+fn number(value: f64) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
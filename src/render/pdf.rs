@@ -0,0 +1,295 @@
+use super::{Role, Shape, Theme};
+use crate::Board;
+
+use pdf_writer::{Content, Finish, Name, Pdf, Rect, Ref, Str};
+
+/// The size of an A4 page, in points.
+const PAGE_WIDTH: f32 = 595.0;
+const PAGE_HEIGHT: f32 = 842.0;
+/// The space left around the edge of each page and between puzzles, in points.
+const MARGIN: f32 = 36.0;
+const GAP: f32 = 18.0;
+const TITLE_SIZE: f32 = 12.0;
+
+const REGULAR: Name = Name(b"F1");
+const BOLD: Name = Name(b"F2");
+
+impl Board {
+    /// Lays the puzzles out on A4 pages, `per_page` to a page, each with its title and difficulty
+    /// written above it, and returns the PDF file. If `solutions` is set, pages with every
+    /// puzzle's solution follow in the same order. Returns None unless `per_page` is 1, 2, 4 or
+    /// 6.
+    pub fn to_pdf(puzzles: &[(String, Self)], per_page: usize, solutions: bool) -> Option<Vec<u8>> {
+        let (columns, rows) = match per_page {
+            1 => (1, 1),
+            2 => (1, 2),
+            4 => (2, 2),
+            6 => (2, 3),
+            _ => return None,
+        };
+
+        let mut slots: Vec<(String, Option<Self>)> = puzzles
+            .iter()
+            .map(|(title, board)| {
+                let title = match board.difficulty() {
+                    Some(difficulty) => format!("{title} - {difficulty}"),
+                    None => title.clone(),
+                };
+                (title, Some(board.clone()))
+            })
+            .collect();
+        if solutions {
+            slots.extend(
+                puzzles
+                    .iter()
+                    .map(|(title, board)| (format!("Solution: {title}"), board.solve())),
+            );
+        }
+        // The solutions start on a page of their own.
+        let mut pages: Vec<_> = slots[..puzzles.len()]
+            .chunks(per_page)
+            .chain(slots[puzzles.len()..].chunks(per_page))
+            .collect();
+        if pages.is_empty() {
+            pages.push(&[]);
+        }
+
+        let catalog_id = Ref::new(1);
+        let page_tree_id = Ref::new(2);
+        let regular_id = Ref::new(3);
+        let bold_id = Ref::new(4);
+        let page_ids: Vec<_> = (0..pages.len() as i32)
+            .map(|i| Ref::new(5 + 2 * i))
+            .collect();
+
+        let mut pdf = Pdf::new();
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(pages.len() as i32);
+        pdf.type1_font(regular_id).base_font(Name(b"Helvetica"));
+        pdf.type1_font(bold_id).base_font(Name(b"Helvetica-Bold"));
+
+        let slot_width = (PAGE_WIDTH - 2.0 * MARGIN - (columns - 1) as f32 * GAP) / columns as f32;
+        let slot_height = (PAGE_HEIGHT - 2.0 * MARGIN - (rows - 1) as f32 * GAP) / rows as f32;
+        for (slots, &page_id) in pages.iter().zip(&page_ids) {
+            let content_id = Ref::new(page_id.get() + 1);
+            let mut content = Content::new();
+
+            for (i, (title, board)) in slots.iter().enumerate() {
+                let left = MARGIN + (i % columns) as f32 * (slot_width + GAP);
+                let top = PAGE_HEIGHT - MARGIN - (i / columns) as f32 * (slot_height + GAP);
+                text(
+                    &mut content,
+                    BOLD,
+                    TITLE_SIZE,
+                    (left, top - TITLE_SIZE),
+                    title,
+                );
+
+                match board {
+                    Some(board) => {
+                        let side = slot_width.min(slot_height - 2.0 * TITLE_SIZE);
+                        let left = left + (slot_width - side) / 2.0;
+                        draw(&mut content, board, (left, top - 2.0 * TITLE_SIZE), side);
+                    }
+                    None => text(
+                        &mut content,
+                        REGULAR,
+                        TITLE_SIZE,
+                        (left, top - 3.0 * TITLE_SIZE),
+                        "This puzzle has no solution.",
+                    ),
+                }
+            }
+
+            let mut page = pdf.page(page_id);
+            page.media_box(Rect::new(0.0, 0.0, PAGE_WIDTH, PAGE_HEIGHT))
+                .parent(page_tree_id)
+                .contents(content_id);
+            page.resources()
+                .fonts()
+                .pair(REGULAR, regular_id)
+                .pair(BOLD, bold_id);
+            page.finish();
+            pdf.stream(content_id, &content.finish());
+        }
+
+        Some(pdf.finish())
+    }
+}
+
+/// Draws a board without its candidates into a square `side` points across, whose top left
+/// corner is at `corner`.
+fn draw(content: &mut Content, board: &Board, corner: (f32, f32), side: f32) {
+    let layout = board.layout(&[], false);
+    let scale = side / layout.width.max(layout.height) as f32;
+    let point = |(x, y): (f64, f64)| (corner.0 + x as f32 * scale, corner.1 - y as f32 * scale);
+
+    for shape in &layout.shapes {
+        match shape {
+            Shape::Rect {
+                x,
+                y,
+                width,
+                height,
+                role,
+            } => {
+                let (x, y) = point((*x, y + height));
+                set_fill(content, *role);
+                content
+                    .rect(x, y, *width as f32 * scale, *height as f32 * scale)
+                    .fill_nonzero();
+            }
+            Shape::Line {
+                points,
+                width,
+                dashed,
+                role,
+            } => {
+                let Some((&first, rest)) = points.split_first() else {
+                    continue;
+                };
+                set_stroke(content, *role);
+                content.set_line_width(*width as f32 * scale);
+                if *dashed {
+                    content.set_dash_pattern([0.08 * scale], 0.0);
+                } else {
+                    content.set_dash_pattern([], 0.0);
+                }
+
+                let (x, y) = point(first);
+                content.move_to(x, y);
+                for &next in rest {
+                    let (x, y) = point(next);
+                    content.line_to(x, y);
+                }
+                content.stroke();
+            }
+            Shape::Circle {
+                center,
+                radius,
+                filled,
+                role,
+            } => {
+                // The colors and line have to be set before the path is started.
+                if *filled {
+                    set_fill(content, *role);
+                    circle(content, point(*center), *radius as f32 * scale);
+                    content.fill_nonzero();
+                } else {
+                    set_fill(content, Role::Background);
+                    set_stroke(content, *role);
+                    content
+                        .set_line_width(super::DECORATION_WIDTH as f32 * scale)
+                        .set_dash_pattern([], 0.0);
+                    circle(content, point(*center), *radius as f32 * scale);
+                    content.fill_nonzero_and_stroke();
+                }
+            }
+            Shape::Text {
+                center,
+                size,
+                text: string,
+                role,
+            } => {
+                let size = *size as f32 * scale;
+                let (x, y) = point(*center);
+                let font = if *role == Role::Given { BOLD } else { REGULAR };
+                set_fill(content, *role);
+                // Helvetica's digits are 0.556 of the font size across, and its capitals are
+                // around 0.667, which is close enough to center them.
+                let width: f32 = string
+                    .chars()
+                    .map(|c| if c.is_ascii_digit() { 0.556 } else { 0.667 })
+                    .sum::<f32>()
+                    * size;
+                text(
+                    content,
+                    font,
+                    size,
+                    (x - width / 2.0, y - 0.36 * size),
+                    string,
+                );
+            }
+        }
+    }
+}
+
+/// Writes a line of text starting at `position`, on its baseline.
+fn text(content: &mut Content, font: Name, size: f32, position: (f32, f32), string: &str) {
+    let bytes: Vec<u8> = string
+        .chars()
+        .map(|c| if c.is_ascii() { c as u8 } else { b'?' })
+        .collect();
+
+    content
+        .begin_text()
+        .set_font(font, size)
+        .next_line(position.0, position.1)
+        .show(Str(&bytes))
+        .end_text();
+}
+
+/// Adds a circle to the current path, made of four Bézier curves.
+fn circle(content: &mut Content, (x, y): (f32, f32), radius: f32) {
+    let k = 0.5523 * radius;
+
+    content
+        .move_to(x + radius, y)
+        .cubic_to(x + radius, y + k, x + k, y + radius, x, y + radius)
+        .cubic_to(x - k, y + radius, x - radius, y + k, x - radius, y)
+        .cubic_to(x - radius, y - k, x - k, y - radius, x, y - radius)
+        .cubic_to(x + k, y - radius, x + radius, y - k, x + radius, y)
+        .close_path();
+}
+
+/// The colors each role is printed in, from 0 to 1.
+fn color(role: Role) -> (f32, f32, f32) {
+    let [red, green, blue] = Theme::print().color(role);
+    (
+        red as f32 / 255.0,
+        green as f32 / 255.0,
+        blue as f32 / 255.0,
+    )
+}
+
+fn set_fill(content: &mut Content, role: Role) {
+    let (r, g, b) = color(role);
+    content.set_fill_rgb(r, g, b);
+}
+
+fn set_stroke(content: &mut Content, role: Role) {
+    let (r, g, b) = color(role);
+    content.set_stroke_rgb(r, g, b);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Board;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn puzzles_are_laid_out_on_pages() {
+        let puzzle =
+            "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79";
+        let board = Board::from_sdm(puzzle).unwrap().remove(0);
+        let puzzles: Vec<_> = (1..=5)
+            .map(|i| (format!("Puzzle {i}"), board.clone()))
+            .collect();
+
+        assert_eq!(None, Board::to_pdf(&puzzles, 3, false));
+
+        let pdf = Board::to_pdf(&puzzles, 4, true).unwrap();
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-"));
+        // Two pages of puzzles, then two of solutions.
+        assert!(text.contains("/Count 4"));
+        assert!(text.contains("(Puzzle 5 - Easy)"));
+        assert!(text.contains("(Solution: Puzzle 5)"));
+
+        let pdf = Board::to_pdf(&puzzles, 6, false).unwrap();
+        assert!(String::from_utf8_lossy(&pdf).contains("/Count 1"));
+    }
+}
@@ -0,0 +1,345 @@
+use super::{Highlight, Role, Shape, Theme};
+use crate::Board;
+
+use ab_glyph::{Font, FontRef, OutlineCurve};
+use tiny_skia::{
+    FillRule, LineCap, LineJoin, Paint, PathBuilder, Pixmap, Rect, Stroke, StrokeDash, Transform,
+};
+
+/// DejaVu Sans, which numbers are written in unless the theme has a font of its own. Its license
+/// is in `fonts/LICENSE` beside it.
+const DEFAULT_FONT: &[u8] = include_bytes!("fonts/DejaVuSans.ttf");
+
+impl Board {
+    /// Draws the board as a PNG image `pixels_per_square` pixels to a square, in the colors and
+    /// font of `theme`, with the same grid, numbers, candidates (if `candidates` is set), variant
+    /// marks and highlights as [`Board::to_svg`]. Returns None if `pixels_per_square` is 0, the
+    /// image would be too large, or the theme's font can't be read.
+    pub fn to_png(
+        &self,
+        pixels_per_square: u32,
+        theme: &Theme,
+        candidates: bool,
+        highlights: &[((usize, usize), Highlight)],
+    ) -> Option<Vec<u8>> {
+        let font = FontRef::try_from_slice(theme.font.as_deref().unwrap_or(DEFAULT_FONT)).ok()?;
+        let layout = self.layout(highlights, candidates);
+        let scale = pixels_per_square as f32;
+        let mut pixmap = Pixmap::new(
+            (layout.width as f32 * scale).round() as u32,
+            (layout.height as f32 * scale).round() as u32,
+        )?;
+        let point = |(x, y): (f64, f64)| (x as f32 * scale, y as f32 * scale);
+
+        for shape in &layout.shapes {
+            match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    role,
+                } => {
+                    let (x, y) = point((*x, *y));
+                    if let Some(rect) =
+                        Rect::from_xywh(x, y, *width as f32 * scale, *height as f32 * scale)
+                    {
+                        pixmap.fill_rect(rect, &paint(theme, *role), Transform::identity(), None);
+                    }
+                }
+                Shape::Line {
+                    points,
+                    width,
+                    dashed,
+                    role,
+                } => {
+                    let points: Vec<_> = points.iter().map(|&next| point(next)).collect();
+                    let dashes = dashed.then(|| vec![0.08 * scale, 0.08 * scale]);
+                    stroke(
+                        &mut pixmap,
+                        &points,
+                        *width as f32 * scale,
+                        dashes,
+                        &paint(theme, *role),
+                    );
+                }
+                Shape::Circle {
+                    center,
+                    radius,
+                    filled,
+                    role,
+                } => {
+                    let (x, y) = point(*center);
+                    let Some(path) = PathBuilder::from_circle(x, y, *radius as f32 * scale) else {
+                        continue;
+                    };
+                    if *filled {
+                        pixmap.fill_path(
+                            &path,
+                            &paint(theme, *role),
+                            FillRule::Winding,
+                            Transform::identity(),
+                            None,
+                        );
+                    } else {
+                        pixmap.fill_path(
+                            &path,
+                            &paint(theme, Role::Background),
+                            FillRule::Winding,
+                            Transform::identity(),
+                            None,
+                        );
+                        let line = Stroke {
+                            width: super::DECORATION_WIDTH as f32 * scale,
+                            ..Stroke::default()
+                        };
+                        pixmap.stroke_path(
+                            &path,
+                            &paint(theme, *role),
+                            &line,
+                            Transform::identity(),
+                            None,
+                        );
+                    }
+                }
+                Shape::Text {
+                    center,
+                    size,
+                    text,
+                    role,
+                } => {
+                    let size = *size as f32 * scale;
+                    let paint = paint(theme, *role);
+                    outline_text(
+                        &mut pixmap,
+                        &font,
+                        point(*center),
+                        size,
+                        text,
+                        *role == Role::Given,
+                        &paint,
+                    );
+                }
+            }
+        }
+
+        pixmap.encode_png().ok()
+    }
+}
+
+/// Paint in the theme's color for the role.
+fn paint(theme: &Theme, role: Role) -> Paint<'static> {
+    let [red, green, blue] = theme.color(role);
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(red, green, blue, 255);
+    paint.anti_alias = true;
+
+    paint
+}
+
+/// Draws a line through the points in order, with round ends and corners.
+fn stroke(
+    pixmap: &mut Pixmap,
+    points: &[(f32, f32)],
+    width: f32,
+    dashes: Option<Vec<f32>>,
+    paint: &Paint,
+) {
+    let Some((&(x, y), rest)) = points.split_first() else {
+        return;
+    };
+    let mut path = PathBuilder::new();
+    path.move_to(x, y);
+    for &(x, y) in rest {
+        path.line_to(x, y);
+    }
+    let Some(path) = path.finish() else {
+        return;
+    };
+
+    let stroke = Stroke {
+        width,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::Round,
+        dash: dashes.and_then(|dashes| StrokeDash::new(dashes, 0.0)),
+        ..Stroke::default()
+    };
+    pixmap.stroke_path(&path, paint, &stroke, Transform::identity(), None);
+}
+
+/// Writes text centered on `center` with the outlines of a font file, thickened if `bold`.
+fn outline_text(
+    pixmap: &mut Pixmap,
+    font: &FontRef,
+    center: (f32, f32),
+    size: f32,
+    text: &str,
+    bold: bool,
+    paint: &Paint,
+) {
+    // Fonts measure their outlines in their own units, with y pointing up.
+    let factor = size / font.height_unscaled();
+    let glyphs: Vec<_> = text.chars().map(|c| font.glyph_id(c)).collect();
+    let width: f32 = glyphs
+        .iter()
+        .map(|&glyph| font.h_advance_unscaled(glyph))
+        .sum();
+    let outlines: Vec<_> = glyphs.iter().map(|&glyph| font.outline(glyph)).collect();
+    let (bottom, top) = outlines
+        .iter()
+        .flatten()
+        .fold(None, |bounds: Option<(f32, f32)>, outline| {
+            let (min, max) = (outline.bounds.min.y, outline.bounds.max.y);
+            Some(bounds.map_or((min, max), |(bottom, top)| (bottom.min(min), top.max(max))))
+        })
+        .unwrap_or_default();
+
+    let mut path = PathBuilder::new();
+    let mut left = -width / 2.0;
+    for (glyph, outline) in glyphs.iter().zip(&outlines) {
+        if let Some(outline) = outline {
+            let point = |point: ab_glyph::Point| {
+                (
+                    center.0 + (left + point.x) * factor,
+                    center.1 - (point.y - (bottom + top) / 2.0) * factor,
+                )
+            };
+            let mut last = None;
+            for curve in &outline.curves {
+                let (start, end) = match curve {
+                    OutlineCurve::Line(start, end)
+                    | OutlineCurve::Quad(start, _, end)
+                    | OutlineCurve::Cubic(start, _, _, end) => (*start, *end),
+                };
+                if last != Some(start) {
+                    if last.is_some() {
+                        path.close();
+                    }
+                    let (x, y) = point(start);
+                    path.move_to(x, y);
+                }
+                match curve {
+                    OutlineCurve::Line(_, end) => {
+                        let (x, y) = point(*end);
+                        path.line_to(x, y);
+                    }
+                    OutlineCurve::Quad(_, control, end) => {
+                        let (x1, y1) = point(*control);
+                        let (x, y) = point(*end);
+                        path.quad_to(x1, y1, x, y);
+                    }
+                    OutlineCurve::Cubic(_, first, second, end) => {
+                        let (x1, y1) = point(*first);
+                        let (x2, y2) = point(*second);
+                        let (x, y) = point(*end);
+                        path.cubic_to(x1, y1, x2, y2, x, y);
+                    }
+                }
+                last = Some(end);
+            }
+            if last.is_some() {
+                path.close();
+            }
+        }
+        left += font.h_advance_unscaled(*glyph);
+    }
+
+    if let Some(path) = path.finish() {
+        pixmap.fill_path(&path, paint, FillRule::Winding, Transform::identity(), None);
+        if bold {
+            let stroke = Stroke {
+                width: 0.04 * size,
+                line_join: LineJoin::Round,
+                ..Stroke::default()
+            };
+            pixmap.stroke_path(&path, paint, &stroke, Transform::identity(), None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Highlight, Number, Theme};
+
+    use pretty_assertions::assert_eq;
+    use tiny_skia::Pixmap;
+
+    #[test]
+    fn pngs_are_drawn_at_the_resolution_asked_for() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+
+        let png = board
+            .to_png(20, &Theme::light(), true, &[((0, 0), Highlight::Hint)])
+            .unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        // Four squares and a quarter of a square of margin on each side.
+        assert_eq!((90, 90), (pixmap.width(), pixmap.height()));
+
+        let color = |x, y| {
+            let pixel = pixmap.pixel(x, y).unwrap();
+            [pixel.red(), pixel.green(), pixel.blue()]
+        };
+        assert_eq!(Theme::light().background, color(1, 1));
+        // The highlighted square in the bottom left corner, beside its candidates.
+        assert_eq!(Theme::light().hint, color(7, 82));
+        // The given's 1 inside the top left square's borders, dark but blended at its edges.
+        assert!((8..22)
+            .flat_map(|x| (8..22).map(move |y| (x, y)))
+            .any(|(x, y)| color(x, y).iter().all(|&channel| channel < 0x40)));
+
+        assert_eq!(None, board.to_png(0, &Theme::light(), true, &[]));
+    }
+
+    #[test]
+    fn pngs_follow_their_theme() {
+        let board = Board::new(3, 3).unwrap();
+        let png = board.to_png(10, &Theme::dark(), true, &[]).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        let pixel = pixmap.pixel(0, 0).unwrap();
+        assert_eq!(
+            Theme::dark().background,
+            [pixel.red(), pixel.green(), pixel.blue()]
+        );
+
+        let theme = Theme {
+            font: Some(b"not a font".to_vec()),
+            ..Theme::light()
+        };
+        assert_eq!(None, board.to_png(10, &theme, true, &[]));
+    }
+
+    #[test]
+    fn pngs_show_little_killer_directions() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::LittleKiller {
+            sum: 5,
+            locations: vec![(1, 0), (0, 1)],
+        }));
+        let theme = Theme::light();
+        let png = board.to_png(100, &theme, false, &[]).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        let color = |x: u32, y: u32| {
+            let pixel = pixmap.pixel(x, y).unwrap();
+            [pixel.red(), pixel.green(), pixel.blue()]
+        };
+
+        // The clue 5\\ is written left of a3, centered half a square from the board, with the
+        // backslash right of the center. Its top is further left than its bottom.
+        let (x, y) = (50, 250);
+        let slash: Vec<_> = (x..x + 25)
+            .flat_map(|x| (y - 25..y + 25).map(move |y| (x, y)))
+            .filter(|&(x, y)| color(x, y) == theme.clue)
+            .collect();
+        let middle = |above: bool| {
+            let xs: Vec<_> = slash
+                .iter()
+                .filter(|&&(_, row)| (row < y) == above)
+                .map(|&(column, _)| column as f64)
+                .collect();
+            xs.iter().sum::<f64>() / xs.len() as f64
+        };
+        assert!(middle(true) < middle(false));
+    }
+}
@@ -0,0 +1,239 @@
+use super::{number, Highlight, Layout, Role, Shape, Theme};
+use crate::Board;
+
+/// How many SVG units a square is across.
+pub(super) const SCALE: f64 = 40.0;
+
+impl Board {
+    /// Draws the board as an SVG image in the colors of `theme`: the grid with thick box
+    /// borders, givens in bold, numbers placed while solving, candidates pencilled small into
+    /// unsolved squares if `candidates` is set, and the marks of any variant rules. Each square in
+    /// `highlights` is colored in underneath, which can be left empty to highlight nothing. Every
+    /// shape has a class naming what it is part of (such as `given`, `entry` or `hint`), so a page
+    /// embedding the image can restyle it. The theme's font is left to whatever shows the image.
+    pub fn to_svg(
+        &self,
+        theme: &Theme,
+        candidates: bool,
+        highlights: &[((usize, usize), Highlight)],
+    ) -> String {
+        let layout = self.layout(highlights, candidates);
+
+        let mut svg = header(&layout, theme);
+        for shape in &layout.shapes {
+            svg.push_str(&element(shape));
+        }
+        svg.push_str("</svg>\n");
+
+        svg
+    }
+}
+
+/// The opening tag of a picture of the layout, with a style sheet in the theme's colors.
+pub(super) fn header(layout: &Layout, theme: &Theme) -> String {
+    let (width, height) = (number(layout.width * SCALE), number(layout.height * SCALE));
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\">\n<style>\n{}</style>\n",
+        style(theme),
+    )
+}
+
+/// The element drawing a shape, on a line of its own.
+pub(super) fn element(shape: &Shape) -> String {
+    match shape {
+        Shape::Rect {
+            x,
+            y,
+            width,
+            height,
+            role,
+        } => format!(
+            "<rect class=\"{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\"/>\n",
+            class(*role),
+            number(x * SCALE),
+            number(y * SCALE),
+            number(width * SCALE),
+            number(height * SCALE),
+        ),
+        Shape::Line {
+            points,
+            width,
+            dashed,
+            role,
+        } => {
+            let points: Vec<_> = points
+                .iter()
+                .map(|(x, y)| format!("{},{}", number(x * SCALE), number(y * SCALE)))
+                .collect();
+            let dashes = if *dashed {
+                format!(" stroke-dasharray=\"{}\"", number(0.08 * SCALE))
+            } else {
+                String::new()
+            };
+            format!(
+                "<polyline class=\"{} line\" points=\"{}\" stroke-width=\"{}\"{dashes}/>\n",
+                class(*role),
+                points.join(" "),
+                number(width * SCALE),
+            )
+        }
+        Shape::Circle {
+            center,
+            radius,
+            filled,
+            role,
+        } => format!(
+            "<circle class=\"{}{}\" cx=\"{}\" cy=\"{}\" r=\"{}\" stroke-width=\"{}\"/>\n",
+            class(*role),
+            if *filled { "" } else { " outline" },
+            number(center.0 * SCALE),
+            number(center.1 * SCALE),
+            number(radius * SCALE),
+            number(super::DECORATION_WIDTH * SCALE),
+        ),
+        Shape::Text {
+            center,
+            size,
+            text,
+            role,
+        } => format!(
+            "<text class=\"{}\" x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>\n",
+            class(*role),
+            number(center.0 * SCALE),
+            number(center.1 * SCALE),
+            number(size * SCALE),
+            escape(text),
+        ),
+    }
+}
+
+/// The class a shape is given for its role.
+fn class(role: Role) -> &'static str {
+    match role {
+        Role::Background => "background",
+        Role::Hint => "hint",
+        Role::Conflict => "conflict",
+        Role::Shade => "shade",
+        Role::Decoration => "decoration",
+        Role::Grid => "grid",
+        Role::Border => "border",
+        Role::Given => "given",
+        Role::Entry => "entry",
+        Role::Candidate => "candidate",
+        Role::Clue => "clue",
+    }
+}
+
+/// The colors of each role, as a style sheet a page embedding the picture can override by
+/// class.
+fn style(theme: &Theme) -> String {
+    let hex = |role: Role| {
+        let [red, green, blue] = theme.color(role);
+        format!("#{red:02x}{green:02x}{blue:02x}")
+    };
+
+    let mut style = String::new();
+    for role in [
+        Role::Background,
+        Role::Hint,
+        Role::Conflict,
+        Role::Shade,
+        Role::Decoration,
+        Role::Grid,
+        Role::Border,
+        Role::Given,
+        Role::Entry,
+        Role::Candidate,
+        Role::Clue,
+    ] {
+        let color = hex(role);
+        let rules = match role {
+            Role::Background | Role::Shade | Role::Decoration => {
+                format!("fill: {color}; stroke: {color}")
+            }
+            Role::Grid | Role::Border => format!("stroke: {color}"),
+            Role::Given => format!("fill: {color}; font-weight: bold"),
+            _ => format!("fill: {color}"),
+        };
+        style.push_str(&format!(".{} {{ {rules} }}\n", class(role)));
+    }
+    style.push_str(".line { fill: none; stroke-linecap: round; stroke-linejoin: round }\n");
+    style.push_str(&format!(".outline {{ fill: {} }}\n", hex(Role::Background)));
+    style.push_str(
+        "text { font-family: sans-serif; text-anchor: middle; dominant-baseline: central }\n",
+    );
+
+    style
+}
+
+/// Escapes the characters XML gives a meaning to.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Highlight, Number, Theme};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn svgs_show_givens_entries_and_candidates() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+        assert!(board.try_collapse(Number::Two, (3, 1)));
+
+        let svg = board.to_svg(&Theme::light(), true, &[((0, 0), Highlight::Conflict)]);
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"180\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg
+            .contains("<rect class=\"conflict\" x=\"10\" y=\"130\" width=\"40\" height=\"40\"/>"));
+        assert!(svg.contains("<text class=\"given\" x=\"30\" y=\"30\" font-size=\"24\">1</text>"));
+        assert!(svg.contains("<text class=\"entry\" x=\"70\" y=\"30\" font-size=\"24\">2</text>"));
+        let candidates: usize = (0..4)
+            .flat_map(|row| (0..4).map(move |column| (row, column)))
+            .map(|location| board.candidates(location).len())
+            .sum();
+        assert_eq!(candidates, svg.matches("class=\"candidate\"").count());
+
+        // The middle lines of the board separate boxes, and the lines between them don't.
+        assert!(svg.contains("class=\"border line\" points=\"90,10 90,50\""));
+        assert!(svg.contains("class=\"grid line\" points=\"50,10 50,50\""));
+    }
+
+    #[test]
+    fn svgs_can_leave_out_candidates_and_follow_their_theme() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+
+        let svg = board.to_svg(&Theme::dark(), false, &[]);
+        assert!(svg.contains("<text class=\"given\""));
+        assert!(!svg.contains("class=\"candidate\""));
+        let [red, green, blue] = Theme::dark().background;
+        assert!(svg.contains(&format!(
+            ".background {{ fill: #{red:02x}{green:02x}{blue:02x};"
+        )));
+    }
+
+    #[test]
+    fn svgs_mark_variant_rules() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 3,
+            locations: vec![(0, 0), (0, 1)],
+        }));
+        for diagonal in Constraint::diagonals(4) {
+            assert!(board.add_constraint(diagonal));
+        }
+
+        let svg = board.to_svg(&Theme::light(), true, &[]);
+        assert!(!svg.contains("class=\"hint\""));
+        assert_eq!(6, svg.matches("stroke-dasharray").count());
+        assert!(svg.contains(">3</text>"));
+        assert!(svg.contains("class=\"decoration line\" points=\"10,170 170,10\""));
+    }
+}
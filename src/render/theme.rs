@@ -0,0 +1,106 @@
+use super::Role;
+
+/// The colors and font a board is drawn with as a picture. Colors are red, green and blue, from
+/// 0 to 255.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Theme {
+    pub background: [u8; 3],
+    /// The square a hint is about.
+    pub hint: [u8; 3],
+    /// Squares whose numbers break a rule.
+    pub conflict: [u8; 3],
+    /// Shaded squares and the bodies of thermometers.
+    pub shade: [u8; 3],
+    /// Diagonals, cages, arrows and the other marks of variant rules.
+    pub decoration: [u8; 3],
+    /// The thin lines between squares.
+    pub grid: [u8; 3],
+    /// The thick lines around boxes and the board.
+    pub border: [u8; 3],
+    pub given: [u8; 3],
+    /// Numbers placed while solving.
+    pub entry: [u8; 3],
+    pub candidate: [u8; 3],
+    /// Clues written outside the board.
+    pub clue: [u8; 3],
+    /// A TrueType or OpenType font to write numbers in, or None for DejaVu Sans, which comes with
+    /// the crate. Only PNGs use it, as SVGs leave fonts to whatever shows them.
+    pub font: Option<Vec<u8>>,
+}
+impl Theme {
+    /// Dark numbers on white, with givens in black and entries in blue.
+    pub fn light() -> Self {
+        Self {
+            background: [0xff, 0xff, 0xff],
+            hint: [0xff, 0xf1, 0xa8],
+            conflict: [0xf6, 0xb8, 0xb8],
+            shade: [0xdd, 0xdd, 0xdd],
+            decoration: [0x55, 0x55, 0x55],
+            grid: [0x99, 0x99, 0x99],
+            border: [0x00, 0x00, 0x00],
+            given: [0x00, 0x00, 0x00],
+            entry: [0x1c, 0x5f, 0xb8],
+            candidate: [0x66, 0x66, 0x66],
+            clue: [0x00, 0x00, 0x00],
+            font: None,
+        }
+    }
+
+    /// Light numbers on a dark gray background.
+    pub fn dark() -> Self {
+        Self {
+            background: [0x1e, 0x1e, 0x1e],
+            hint: [0x5c, 0x52, 0x20],
+            conflict: [0x6b, 0x2b, 0x2b],
+            shade: [0x3a, 0x3a, 0x3a],
+            decoration: [0xaa, 0xaa, 0xaa],
+            grid: [0x5a, 0x5a, 0x5a],
+            border: [0xe0, 0xe0, 0xe0],
+            given: [0xf0, 0xf0, 0xf0],
+            entry: [0x7f, 0xb2, 0xff],
+            candidate: [0xa0, 0xa0, 0xa0],
+            clue: [0xe0, 0xe0, 0xe0],
+            font: None,
+        }
+    }
+
+    /// Grays that print well in black and white, with no highlights.
+    pub fn print() -> Self {
+        Self {
+            background: [0xff, 0xff, 0xff],
+            hint: [0xff, 0xff, 0xff],
+            conflict: [0xff, 0xff, 0xff],
+            shade: [0xd9, 0xd9, 0xd9],
+            decoration: [0x59, 0x59, 0x59],
+            grid: [0x8c, 0x8c, 0x8c],
+            border: [0x00, 0x00, 0x00],
+            given: [0x00, 0x00, 0x00],
+            entry: [0x1a, 0x59, 0xb3],
+            candidate: [0x59, 0x59, 0x59],
+            clue: [0x00, 0x00, 0x00],
+            font: None,
+        }
+    }
+
+    /// The color shapes with the role are drawn in.
+    pub(crate) fn color(&self, role: Role) -> [u8; 3] {
+        match role {
+            Role::Background => self.background,
+            Role::Hint => self.hint,
+            Role::Conflict => self.conflict,
+            Role::Shade => self.shade,
+            Role::Decoration => self.decoration,
+            Role::Grid => self.grid,
+            Role::Border => self.border,
+            Role::Given => self.given,
+            Role::Entry => self.entry,
+            Role::Candidate => self.candidate,
+            Role::Clue => self.clue,
+        }
+    }
+}
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
@@ -0,0 +1,170 @@
+use super::{number, Role, Shape, Theme};
+use crate::Board;
+
+/// How many points tall text a square across is, as squares are drawn a centimeter across.
+const POINTS_PER_SQUARE: f64 = 28.45;
+
+impl Board {
+    /// Writes the puzzle as a TikZ picture for a LaTeX document, with the same grid, numbers and
+    /// marks of variant rules (such as cages and diagonals) as [`Board::to_pdf`] prints. Each
+    /// square is a centimeter across, so the picture can be sized with `\resizebox`. The colors
+    /// are defined inside the picture as `sudoku` followed by what they are for, such as
+    /// `sudokugiven` or `sudokushade`, so the document needs nothing but the `tikz` package.
+    pub fn to_tikz(&self) -> String {
+        let layout = self.layout(&[], false);
+        let theme = Theme::print();
+        // TikZ's y points up, where the layout's points down.
+        let point = |(x, y): (f64, f64)| format!("({},{})", number(x), number(layout.height - y));
+
+        let mut tikz = "\\begin{tikzpicture}[x=1cm, y=1cm]\n".to_string();
+        for role in [
+            Role::Background,
+            Role::Shade,
+            Role::Decoration,
+            Role::Grid,
+            Role::Border,
+            Role::Given,
+            Role::Entry,
+            Role::Clue,
+        ] {
+            let [red, green, blue] = theme.color(role);
+            tikz.push_str(&format!(
+                "\\definecolor{{{}}}{{RGB}}{{{red},{green},{blue}}}\n",
+                name(role)
+            ));
+        }
+
+        for shape in &layout.shapes {
+            tikz.push_str(&match shape {
+                Shape::Rect {
+                    x,
+                    y,
+                    width,
+                    height,
+                    role,
+                } => format!(
+                    "\\fill[{}] {} rectangle {};\n",
+                    name(*role),
+                    point((*x, *y)),
+                    point((x + width, y + height)),
+                ),
+                Shape::Line {
+                    points,
+                    width,
+                    dashed,
+                    role,
+                } => {
+                    let points: Vec<_> = points.iter().map(|&next| point(next)).collect();
+                    let dashes = if *dashed {
+                        ", dash pattern=on 0.08cm off 0.08cm"
+                    } else {
+                        ""
+                    };
+                    format!(
+                        "\\draw[{}, line width={}cm, line cap=round, line join=round{dashes}] {};\n",
+                        name(*role),
+                        number(*width),
+                        points.join(" -- "),
+                    )
+                }
+                Shape::Circle {
+                    center,
+                    radius,
+                    filled: true,
+                    role,
+                } => format!(
+                    "\\fill[{}] {} circle[radius={}];\n",
+                    name(*role),
+                    point(*center),
+                    number(*radius),
+                ),
+                Shape::Circle {
+                    center,
+                    radius,
+                    filled: false,
+                    role,
+                } => format!(
+                    "\\filldraw[fill={}, draw={}, line width={}cm] {} circle[radius={}];\n",
+                    name(Role::Background),
+                    name(*role),
+                    number(super::DECORATION_WIDTH),
+                    point(*center),
+                    number(*radius),
+                ),
+                Shape::Text {
+                    center,
+                    size,
+                    text,
+                    role,
+                } => {
+                    let size = number(size * POINTS_PER_SQUARE);
+                    let weight = if *role == Role::Given { "\\bfseries" } else { "" };
+                    format!(
+                        "\\node[text={}, inner sep=0pt, font=\\fontsize{{{size}}}{{{size}}}\\selectfont\
+                         \\sffamily{weight}] at {} {{{text}}};\n",
+                        name(*role),
+                        point(*center),
+                    )
+                }
+            });
+        }
+        tikz.push_str("\\end{tikzpicture}\n");
+
+        tikz
+    }
+}
+
+/// The name of the color a role is drawn in.
+fn name(role: Role) -> &'static str {
+    match role {
+        Role::Background => "sudokubackground",
+        Role::Hint => "sudokuhint",
+        Role::Conflict => "sudokuconflict",
+        Role::Shade => "sudokushade",
+        Role::Decoration => "sudokudecoration",
+        Role::Grid => "sudokugrid",
+        Role::Border => "sudokuborder",
+        Role::Given => "sudokugiven",
+        Role::Entry => "sudokuentry",
+        Role::Candidate => "sudokucandidate",
+        Role::Clue => "sudokuclue",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Number};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tikz_pictures_show_numbers_and_variant_rules() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.try_give(Number::One, (3, 0)));
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 3,
+            locations: vec![(0, 0), (0, 1)],
+        }));
+        for diagonal in Constraint::diagonals(4) {
+            assert!(board.add_constraint(diagonal));
+        }
+
+        let tikz = board.to_tikz();
+        assert!(tikz.starts_with("\\begin{tikzpicture}[x=1cm, y=1cm]\n"));
+        assert!(tikz.ends_with("\\end{tikzpicture}\n"));
+        assert!(tikz.contains("\\definecolor{sudokugiven}{RGB}{0,0,0}\n"));
+        assert!(tikz.contains(
+            "\\node[text=sudokugiven, inner sep=0pt, \
+             font=\\fontsize{17.07}{17.07}\\selectfont\\sffamily\\bfseries] at (0.75,3.75) {1};"
+        ));
+        // The cage's sum, and the dashes around it.
+        assert!(tikz.contains("{3};"));
+        assert_eq!(6, tikz.matches("dash pattern").count());
+        assert!(tikz.contains(
+            "\\draw[sudokudecoration, line width=0.04cm, line cap=round, line join=round] \
+             (0.25,0.25) -- (4.25,4.25);"
+        ));
+        // Candidates are left for the reader to pencil in.
+        assert!(!tikz.contains("sudokucandidate"));
+    }
+}
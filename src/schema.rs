@@ -0,0 +1,250 @@
+use crate::{Board, Constraint, Mark, MultiBoard, Number, PencilMarks, Square};
+
+use serde::Deserialize;
+
+/// A board as it is written with serde, checked before it is turned back into a [`Board`]. In
+/// JSON it looks like this, with `squares` indexed by row from the bottom and then by column, in
+/// the same `(row, column)` order as the locations in `constraints`:
+///
+/// ```json
+/// {
+///   "box_height": 2,
+///   "box_width": 2,
+///   "squares": [
+///     [{ "given": 1 }, { "value": 2 }, { "candidates": [3, 4] }, { "candidates": [3, 4] }],
+///     ...
+///   ],
+///   "constraints": ["rows", "columns", "boxes", { "cage": { "sum": 3, "locations": [[0, 0], [0, 1]] } }]
+/// }
+/// ```
+#[derive(Deserialize)]
+pub(crate) struct BoardData {
+    box_height: usize,
+    box_width: usize,
+    squares: Vec<Vec<Square>>,
+    constraints: Vec<Constraint>,
+}
+impl TryFrom<BoardData> for Board {
+    type Error = String;
+
+    /// Rebuilds the board from its numbers and constraints, then narrows each unsolved square
+    /// down to the candidates written for it, which stay ruled out when numbers are undone.
+    fn try_from(data: BoardData) -> Result<Self, Self::Error> {
+        let mut board = Board::new(data.box_height, data.box_width).ok_or_else(|| {
+            format!(
+                "boxes of {} by {} squares don't make a board",
+                data.box_height, data.box_width
+            )
+        })?;
+        let size = board.size();
+
+        if data.squares.len() != size || data.squares.iter().any(|row| row.len() != size) {
+            return Err(format!("squares should be {size} rows of {size}"));
+        }
+        if let Some(constraint) = data
+            .constraints
+            .iter()
+            .find(|constraint| !constraint.fits(size))
+        {
+            return Err(format!("{constraint:?} doesn't fit on the board"));
+        }
+        let in_range = |numbers: &[Number]| numbers.iter().all(|number| number.index() < size);
+
+        board.constraints = data.constraints;
+        let mut candidates = Vec::new();
+        for (row, squares) in data.squares.into_iter().enumerate() {
+            for (column, square) in squares.into_iter().enumerate() {
+                let numbers = match &square {
+                    Square::Given(number) | Square::Number(number) => vec![*number],
+                    Square::Superposition(_) => square.candidates(),
+                };
+                if !in_range(&numbers) {
+                    return Err(format!("({row}, {column}) holds a number above {size}"));
+                }
+
+                if let Square::Superposition(_) = square {
+                    candidates.push(((row, column), numbers));
+                } else {
+                    board.board[row][column] = square;
+                }
+            }
+        }
+
+        board.update_superpositions();
+        for ((row, column), numbers) in candidates {
+            if !board.eliminate((row, column), &numbers) && !numbers.is_empty() {
+                return Err(format!(
+                    "none of the candidates of ({row}, {column}) are allowed there"
+                ));
+            }
+        }
+        if !board.is_valid() {
+            return Err("the numbers on the board break its constraints".to_string());
+        }
+
+        Ok(board)
+    }
+}
+
+/// Boards overlapping on a larger grid as they are written with serde, each board written as
+/// above along with where its bottom left square sits on the larger grid:
+///
+/// ```json
+/// { "grids": [{ "box_height": 3, ... }, { "box_height": 3, ... }], "offsets": [[6, 0], [0, 6]] }
+/// ```
+#[derive(Deserialize)]
+pub(crate) struct MultiBoardData {
+    grids: Vec<Board>,
+    offsets: Vec<(usize, usize)>,
+}
+impl TryFrom<MultiBoardData> for MultiBoard {
+    type Error = String;
+
+    fn try_from(data: MultiBoardData) -> Result<Self, Self::Error> {
+        MultiBoard::from_grids(data.grids, data.offsets).ok_or_else(|| {
+            "the boards don't line up, or disagree on a square they share".to_string()
+        })
+    }
+}
+
+/// Pencil marks as they are written with serde, with the marks of each kind indexed by row from
+/// the bottom and then by column:
+///
+/// ```json
+/// { "size": 4, "corner": [[[1, 3], [], [], []], ...], "center": [[[], [2], [], []], ...] }
+/// ```
+#[derive(Deserialize)]
+pub(crate) struct PencilMarksData {
+    size: usize,
+    corner: Vec<Vec<Vec<Number>>>,
+    center: Vec<Vec<Vec<Number>>>,
+}
+impl TryFrom<PencilMarksData> for PencilMarks {
+    type Error = String;
+
+    fn try_from(data: PencilMarksData) -> Result<Self, Self::Error> {
+        let size = data.size;
+        let mut marks = PencilMarks::new(size);
+
+        for (mark, rows) in [(Mark::Corner, data.corner), (Mark::Center, data.center)] {
+            if rows.len() != size || rows.iter().any(|row| row.len() != size) {
+                return Err(format!("{mark:?} marks should be {size} rows of {size}"));
+            }
+
+            for (row, squares) in rows.into_iter().enumerate() {
+                for (column, numbers) in squares.into_iter().enumerate() {
+                    for number in numbers {
+                        if number.index() >= size {
+                            return Err(format!("({row}, {column}) holds a number above {size}"));
+                        }
+                        marks.add(mark, number, (row, column));
+                    }
+                }
+            }
+        }
+
+        Ok(marks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Constraint, Dot, Mark, MultiBoard, Number, PencilMarks};
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn boards_round_trip_through_json() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::Cage {
+            sum: 3,
+            locations: vec![(0, 0), (0, 1)],
+        }));
+        assert!(board.add_constraint(Constraint::Dot {
+            dot: Dot::White,
+            locations: [(3, 0), (3, 1)],
+        }));
+        assert!(board.try_give(Number::One, (0, 0)));
+        assert!(board.try_collapse(Number::Two, (0, 1)));
+        assert!(board.try_collapse(Number::Four, (3, 3)));
+        assert!(board.eliminate((2, 2), &[Number::One, Number::Two]));
+
+        let json = serde_json::to_value(&board).unwrap();
+        assert_eq!(serde_json::json!({ "given": 1 }), json["squares"][0][0]);
+        assert_eq!(serde_json::json!({ "value": 2 }), json["squares"][0][1]);
+        assert_eq!(serde_json::json!({ "value": 4 }), json["squares"][3][3]);
+        assert_eq!(
+            serde_json::json!({ "candidates": [1, 2] }),
+            json["squares"][2][2]
+        );
+        assert_eq!(serde_json::json!("rows"), json["constraints"][0]);
+        assert_eq!(
+            serde_json::json!({ "dot": { "dot": "white", "locations": [[3, 0], [3, 1]] } }),
+            json["constraints"][4]
+        );
+
+        let mut read: Board = serde_json::from_value(json).unwrap();
+        assert_eq!(board, read);
+        assert!(read.is_given((0, 0)));
+
+        // Undoing the 4 lets it back in, but the 3 was ruled out by the candidates written.
+        assert!(read.undo((3, 3)));
+        assert_eq!(
+            vec![Number::One, Number::Two, Number::Four],
+            read.candidates((2, 2))
+        );
+    }
+
+    #[test]
+    fn broken_json_boards_are_refused() {
+        let board = Board::new(2, 2).unwrap();
+        let mut json = serde_json::to_value(&board).unwrap();
+        json["squares"][0][0] = serde_json::json!({ "value": 1 });
+        json["squares"][0][1] = serde_json::json!({ "given": 1 });
+        assert!(serde_json::from_value::<Board>(json.clone()).is_err());
+
+        json["squares"][0][1] = serde_json::json!({ "value": 5 });
+        assert!(serde_json::from_value::<Board>(json.clone()).is_err());
+
+        json["squares"][0][1] = serde_json::json!({ "value": 2 });
+        json["squares"][1][0] = serde_json::json!({ "candidates": [1] });
+        assert!(serde_json::from_value::<Board>(json.clone()).is_err());
+
+        json["squares"][1][0] = serde_json::json!({ "candidates": [3] });
+        json["squares"].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<Board>(json).is_err());
+    }
+
+    #[test]
+    fn games_round_trip_through_json() {
+        let mut twodoku = MultiBoard::twodoku();
+        assert!(twodoku.try_give(Number::Five, (7, 7)));
+        assert!(twodoku.add_constraint(0, Constraint::AntiKnight));
+
+        let json = serde_json::to_value(&twodoku).unwrap();
+        assert_eq!(serde_json::json!([[6, 0], [0, 6]]), json["offsets"]);
+        assert_eq!(twodoku, serde_json::from_value(json.clone()).unwrap());
+
+        let mut disagreeing = json.clone();
+        disagreeing["grids"][1]["squares"][7][1] = serde_json::json!({ "given": 4 });
+        assert!(serde_json::from_value::<MultiBoard>(disagreeing).is_err());
+
+        let mut misaligned = json;
+        misaligned["offsets"][1] = serde_json::json!([0, 5]);
+        assert!(serde_json::from_value::<MultiBoard>(misaligned).is_err());
+
+        let mut marks = PencilMarks::new(4);
+        assert!(marks.add(Mark::Corner, Number::Three, (0, 0)));
+        assert!(marks.add(Mark::Corner, Number::One, (0, 0)));
+        assert!(marks.add(Mark::Center, Number::Two, (3, 1)));
+
+        let mut json = serde_json::to_value(&marks).unwrap();
+        assert_eq!(serde_json::json!([1, 3]), json["corner"][0][0]);
+        assert_eq!(marks, serde_json::from_value(json.clone()).unwrap());
+
+        json["center"][3][1] = serde_json::json!([5]);
+        assert!(serde_json::from_value::<PencilMarks>(json.clone()).is_err());
+        json["center"].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<PencilMarks>(json).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Board, Number};
+
+use std::fmt::Display;
+
+/// How hard a puzzle is to solve by hand, judged by the techniques it needs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Every step is a square with only one candidate left.
+    Easy,
+    /// Some steps are a number with only one place left in a row, column, box or other region.
+    Medium,
+    /// Singles alone get stuck, so harder techniques or trial and error are needed.
+    Hard,
+}
+impl Display for Difficulty {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Easy => "Easy",
+            Self::Medium => "Medium",
+            Self::Hard => "Hard",
+        })
+    }
+}
+
+impl Board {
+    /// Counts the ways the board can be completed, stopping once `limit` is reached. A puzzle has
+    /// a unique solution exactly when this returns 1 with a limit of 2.
+    pub fn count_solutions(&self, limit: usize) -> usize {
+        let mut solutions = Vec::new();
+        self.search(limit, &mut solutions);
+
+        solutions.len()
+    }
+
+    /// Finds a completion of the board that keeps to all of its constraints, if there is one.
+    pub fn solve(&self) -> Option<Self> {
+        let mut solutions = Vec::new();
+        self.search(1, &mut solutions);
+
+        solutions.pop()
+    }
+
+    /// Rates how hard the puzzle is by solving it the way a person would, placing singles for as
+    /// long as there are any. Returns None unless the puzzle has exactly one solution.
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        if self.count_solutions(2) != 1 {
+            return None;
+        }
+
+        let size = self.size();
+        let regions: Vec<_> = self
+            .constraints
+            .iter()
+            .flat_map(|constraint| constraint.regions(self))
+            .filter(|region| region.len() == size)
+            .collect();
+        let mut board = self.clone();
+        let mut difficulty = Difficulty::Easy;
+
+        while !board.is_solved() {
+            let naked = (0..size)
+                .flat_map(|row| (0..size).map(move |column| (row, column)))
+                .find_map(|location| match board.candidates(location)[..] {
+                    [number] if board.number(location).is_none() => Some((number, location)),
+                    _ => None,
+                });
+            if let Some((number, location)) = naked {
+                board.try_collapse(number, location);
+                continue;
+            }
+
+            let hidden = regions.iter().find_map(|region| {
+                Number::ALL[..size].iter().find_map(|&number| {
+                    let mut places = region
+                        .iter()
+                        .filter(|&&location| board.candidates(location).contains(&number));
+                    let placed = region
+                        .iter()
+                        .any(|&location| board.number(location) == Some(number));
+
+                    match (places.next(), places.next()) {
+                        (Some(&location), None) if !placed => Some((number, location)),
+                        _ => None,
+                    }
+                })
+            });
+            match hidden {
+                Some((number, location)) => {
+                    board.try_collapse(number, location);
+                    difficulty = Difficulty::Medium;
+                }
+                None => return Some(Difficulty::Hard),
+            }
+        }
+
+        Some(difficulty)
+    }
+
+    /// Depth-first search that always branches on one of the squares with the fewest candidates
+    /// left, so that dead ends are found as early as possible.
+    fn search(&self, limit: usize, solutions: &mut Vec<Self>) {
+        if solutions.len() >= limit {
+            return;
+        }
+
+        if self.is_solved() {
+            if self.is_valid() {
+                solutions.push(self.clone());
+            }
+            return;
+        }
+
+        let Some(&location) = self
+            .find_lowest_superpositions()
+            .as_ref()
+            .and_then(|locations| locations.first())
+        else {
+            return;
+        };
+
+        for number in self.candidates(location) {
+            let mut next = self.clone();
+            if next.try_collapse(number, location) {
+                next.search(limit, solutions);
+            }
+
+            if solutions.len() >= limit {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::Constraint;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn solutions_keep_to_every_constraint() {
+        let mut board = Board::new(2, 2).unwrap();
+        assert!(board.add_constraint(Constraint::Region(vec![(0, 0), (1, 1), (2, 2), (3, 3)])));
+        assert!(board.try_collapse(Number::One, (0, 0)));
+        assert!(!board.candidates((3, 3)).contains(&Number::One));
+
+        let solution = board.solve().unwrap();
+        assert!(solution.is_solved());
+        assert!(solution.is_valid());
+        let diagonal: Vec<_> = (0..4).filter_map(|i| solution.number((i, i))).collect();
+        assert_eq!(4, diagonal.len());
+        assert!(Number::ALL[..4]
+            .iter()
+            .all(|number| diagonal.contains(number)));
+    }
+
+    #[test]
+    fn count_solutions_stops_at_limit() {
+        assert_eq!(2, Board::new(2, 2).unwrap().count_solutions(2));
+
+        let solution = [[1, 2, 3, 4], [3, 4, 1, 2], [2, 1, 4, 3], [4, 3, 2, 1]];
+        let mut board = Board::new(2, 2).unwrap();
+        for (row, values) in solution.iter().enumerate() {
+            for (column, &value) in values.iter().enumerate() {
+                if row != column {
+                    assert!(board.try_collapse(Number::ALL[value - 1], (row, column)));
+                }
+            }
+        }
+        assert_eq!(1, board.count_solutions(2));
+
+        let solved = board.solve().unwrap();
+        for (row, values) in solution.iter().enumerate() {
+            for (column, &value) in values.iter().enumerate() {
+                assert_eq!(Some(Number::ALL[value - 1]), solved.number((row, column)));
+            }
+        }
+    }
+
+    #[test]
+    fn difficulty_follows_the_techniques_needed() {
+        let read = |puzzle: &str| Board::from_sdm(puzzle).unwrap().remove(0);
+        assert_eq!(None, Board::default().difficulty());
+        assert_eq!(
+            Some(Difficulty::Easy),
+            read(
+                "53..7....6..195....98....6.8...6...34..8.3..17...2...6.6....28....419..5....8..79"
+            )
+            .difficulty()
+        );
+        assert_eq!(
+            Some(Difficulty::Hard),
+            read(
+                "8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4.."
+            )
+            .difficulty()
+        );
+    }
+}
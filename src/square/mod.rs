@@ -6,15 +6,36 @@ use superposition::Superposition;
 
 use std::fmt::Display;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum Square {
+    /// A number given as part of the puzzle, which can't be undone.
+    Given(Number),
+    #[cfg_attr(feature = "serde", serde(rename = "value"))]
     Number(Number),
+    #[cfg_attr(feature = "serde", serde(rename = "candidates"))]
     Superposition(Superposition),
 }
 impl Square {
+    /// An unsolved square on a board of the given size.
+    pub fn new(size: usize) -> Self {
+        Self::Superposition(Superposition::new(size))
+    }
+
+    pub fn candidates(&self) -> Vec<Number> {
+        match self {
+            Self::Given(_collapsed) | Self::Number(_collapsed) => Vec::new(),
+            Self::Superposition(superposition) => superposition.iter().collect(),
+        }
+    }
+
     pub fn collapse_random(&mut self) -> Option<Number> {
         match self {
-            Self::Number(_collapsed) => None,
+            Self::Given(_collapsed) | Self::Number(_collapsed) => None,
             Self::Superposition(superposition) => {
                 let number = superposition.collapse_random()?;
 
@@ -27,28 +48,37 @@ impl Square {
 
     pub fn collapsed_number(&self) -> Option<Number> {
         match self {
-            Self::Number(collapsed) => Some(*collapsed),
+            Self::Given(collapsed) | Self::Number(collapsed) => Some(*collapsed),
             Self::Superposition(_superposition) => None,
         }
     }
 
+    pub fn relabel(&mut self, mapping: &[Number]) {
+        match self {
+            Self::Given(collapsed) | Self::Number(collapsed) => {
+                *collapsed = mapping[collapsed.index()]
+            }
+            Self::Superposition(superposition) => superposition.relabel(mapping),
+        }
+    }
+
     pub fn remove(&mut self, number: Number) -> bool {
         match self {
-            Self::Number(_collapsed) => false,
+            Self::Given(_collapsed) | Self::Number(_collapsed) => false,
             Self::Superposition(superposition) => superposition.remove(number),
         }
     }
 
     pub fn superposition_number(&self) -> Option<usize> {
         match self {
-            Self::Number(_collapsed) => None,
+            Self::Given(_collapsed) | Self::Number(_collapsed) => None,
             Self::Superposition(superposition) => Some(superposition.superposition_number()),
         }
     }
 
     pub fn try_collapse(&mut self, number: Number) -> bool {
         match self {
-            Self::Number(_collapsed) => false,
+            Self::Given(_collapsed) | Self::Number(_collapsed) => false,
             Self::Superposition(superposition) => {
                 if superposition.contains(number) {
                     *self = Self::Number(number);
@@ -60,25 +90,40 @@ impl Square {
         }
     }
 
-    pub fn undo_collapse(&mut self) -> bool {
+    /// Collapses the square like [`Square::try_collapse`], but as a given.
+    pub fn try_give(&mut self, number: Number) -> bool {
+        if self.try_collapse(number) {
+            *self = Self::Given(number);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn is_given(&self) -> bool {
+        matches!(self, Self::Given(_given))
+    }
+
+    pub fn undo_collapse(&mut self, size: usize) -> bool {
         match self {
             Self::Number(_collapsed) => {
-                *self = Self::Superposition(Superposition::default());
+                *self = Self::new(size);
                 true
             }
+            Self::Given(_given) => false,
             Self::Superposition(_superposition) => false,
         }
     }
 }
 impl Default for Square {
     fn default() -> Self {
-        Self::Superposition(Superposition::default())
+        Self::new(9)
     }
 }
 impl Display for Square {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Number(displayable) => displayable.fmt(f),
+            Self::Given(displayable) | Self::Number(displayable) => displayable.fmt(f),
             Self::Superposition(displayable) => displayable.fmt(f),
         }
     }
@@ -102,6 +147,7 @@ mod tests {
             Square::Number(Number::Seven),
             Square::Number(Number::Eight),
             Square::Number(Number::Nine),
+            Square::Given(Number::Nine),
             Square::default(),
             {
                 // Square with only one superposition option
@@ -124,7 +170,9 @@ mod tests {
         .map(|square| format!("{square}"))
         .collect();
 
-        let correct_displays = vec!["1", "2", "3", "4", "5", "6", "7", "8", "9", "?", "!", "0"];
+        let correct_displays = vec![
+            "1", "2", "3", "4", "5", "6", "7", "8", "9", "9", "?", "!", "0",
+        ];
 
         assert_eq!(correct_displays, square_displays);
     }
@@ -1,9 +1,10 @@
 use std::fmt::Display;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
-#[repr(u8)]
+/// A number that can be placed on a board. Boards of size `n` use the first `n` numbers, which are
+/// written 1 to 9 followed by A to P.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Number {
-    One = b'1',
+    One,
     Two,
     Three,
     Four,
@@ -12,9 +13,25 @@ pub enum Number {
     Seven,
     Eight,
     Nine,
+    Ten,
+    Eleven,
+    Twelve,
+    Thirteen,
+    Fourteen,
+    Fifteen,
+    Sixteen,
+    Seventeen,
+    Eighteen,
+    Nineteen,
+    Twenty,
+    TwentyOne,
+    TwentyTwo,
+    TwentyThree,
+    TwentyFour,
+    TwentyFive,
 }
 impl Number {
-    pub const ALL: [Self; 9] = [
+    pub const ALL: [Self; 25] = [
         Self::One,
         Self::Two,
         Self::Three,
@@ -24,10 +41,84 @@ impl Number {
         Self::Seven,
         Self::Eight,
         Self::Nine,
+        Self::Ten,
+        Self::Eleven,
+        Self::Twelve,
+        Self::Thirteen,
+        Self::Fourteen,
+        Self::Fifteen,
+        Self::Sixteen,
+        Self::Seventeen,
+        Self::Eighteen,
+        Self::Nineteen,
+        Self::Twenty,
+        Self::TwentyOne,
+        Self::TwentyTwo,
+        Self::TwentyThree,
+        Self::TwentyFour,
+        Self::TwentyFive,
     ];
+
+    /// Reads a number written as 1 to 9 or A to P (in either case).
+    pub fn from_char(character: char) -> Option<Self> {
+        let index = match character {
+            '1'..='9' => character as usize - '1' as usize,
+            'A'..='P' => character as usize - 'A' as usize + 9,
+            'a'..='p' => character as usize - 'a' as usize + 9,
+            _ => return None,
+        };
+
+        Self::from_index(index)
+    }
+
+    /// The number at `index` in [`Number::ALL`].
+    pub fn from_index(index: usize) -> Option<Self> {
+        Self::ALL.get(index).copied()
+    }
+
+    /// The position of this number in [`Number::ALL`].
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The number as 1 to 9 or A to P.
+    pub fn to_char(self) -> char {
+        match self.index() {
+            index @ 0..=8 => (b'1' + index as u8) as char,
+            index => (b'A' + (index - 9) as u8) as char,
+        }
+    }
+
+    /// The numeric value of the number, from 1 to 25.
+    pub fn value(self) -> usize {
+        self.index() + 1
+    }
 }
 impl Display for Number {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("{}", *self as u8 as char))
+        f.write_str(&format!("{}", self.to_char()))
+    }
+}
+/// Numbers are written as their value, from 1 to 25.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Number {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.value() as u8)
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Number {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = u8::deserialize(deserializer)?;
+
+        (value as usize)
+            .checked_sub(1)
+            .and_then(Self::from_index)
+            .ok_or_else(|| {
+                serde::de::Error::invalid_value(
+                    serde::de::Unexpected::Unsigned(value.into()),
+                    &"a number from 1 to 25",
+                )
+            })
     }
 }
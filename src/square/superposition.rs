@@ -4,11 +4,23 @@ use rand::prelude::*;
 
 use std::{collections::BTreeSet, fmt::Display};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Superposition {
     superposition: BTreeSet<Number>,
 }
 impl Superposition {
+    /// A superposition of every number on a board of the given size.
+    pub fn new(size: usize) -> Self {
+        Self {
+            superposition: Number::ALL[..size].iter().copied().collect(),
+        }
+    }
+
     pub fn contains(&self, number: Number) -> bool {
         self.superposition.contains(&number)
     }
@@ -22,6 +34,18 @@ impl Superposition {
             .cloned()
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = Number> + '_ {
+        self.superposition.iter().copied()
+    }
+
+    pub fn relabel(&mut self, mapping: &[Number]) {
+        self.superposition = self
+            .superposition
+            .iter()
+            .map(|number| mapping[number.index()])
+            .collect();
+    }
+
     pub fn remove(&mut self, number: Number) -> bool {
         self.superposition.remove(&number)
     }
@@ -32,9 +56,7 @@ impl Superposition {
 }
 impl Default for Superposition {
     fn default() -> Self {
-        Self {
-            superposition: BTreeSet::from(Number::ALL),
-        }
+        Self::new(9)
     }
 }
 impl Display for Superposition {
@@ -0,0 +1,598 @@
+use crate::{Board, Constraint, Number};
+
+use std::cmp::Ordering;
+
+impl Board {
+    /// Rotates the board a quarter turn clockwise, as it is displayed. Boxes that are not square
+    /// are turned on their side.
+    pub fn rotate(&mut self) {
+        let last = self.size() - 1;
+        self.rearrange(|(row, column)| (column, last - row));
+        std::mem::swap(&mut self.box_height, &mut self.box_width);
+    }
+
+    /// Mirrors the board left to right, swapping the first column with the last.
+    pub fn reflect_horizontally(&mut self) {
+        let last = self.size() - 1;
+        self.rearrange(|(row, column)| (row, last - column));
+    }
+
+    /// Mirrors the board top to bottom, swapping the first row with the last.
+    pub fn reflect_vertically(&mut self) {
+        let last = self.size() - 1;
+        self.rearrange(|(row, column)| (last - row, column));
+    }
+
+    /// Mirrors the board across the a1 diagonal, turning rows into columns. Boxes that are not
+    /// square are turned on their side.
+    pub fn transpose(&mut self) {
+        self.rearrange(|(row, column)| (column, row));
+        std::mem::swap(&mut self.box_height, &mut self.box_width);
+    }
+
+    /// Reorders the bands (the rows of boxes, starting from row 1) so that band `i` becomes the
+    /// band that was at `permutation[i]`. Returns false without changing anything if
+    /// `permutation` is not a permutation of every band, or if the board has constraints such as
+    /// anti-knight that depend on which squares are next to each other.
+    pub fn permute_bands(&mut self, permutation: &[usize]) -> bool {
+        let height = self.box_height;
+        if self.uses_adjacency()
+            || permutation.len() != self.size() / height
+            || !is_permutation(permutation)
+        {
+            return false;
+        }
+
+        self.rearrange(|(row, column)| (permutation[row / height] * height + row % height, column));
+
+        true
+    }
+
+    /// Reorders the stacks (the columns of boxes, starting from column a) so that stack `i`
+    /// becomes the stack that was at `permutation[i]`. Returns false without changing anything if
+    /// `permutation` is not a permutation of every stack, or if the board has constraints that
+    /// depend on which squares are next to each other.
+    pub fn permute_stacks(&mut self, permutation: &[usize]) -> bool {
+        let width = self.box_width;
+        if self.uses_adjacency()
+            || permutation.len() != self.size() / width
+            || !is_permutation(permutation)
+        {
+            return false;
+        }
+
+        self.rearrange(|(row, column)| (row, permutation[column / width] * width + column % width));
+
+        true
+    }
+
+    /// Reorders the rows within one band so that row `i` of the band becomes the row that was at
+    /// `permutation[i]`. Returns false without changing anything if `band` is out of range,
+    /// `permutation` is not a permutation of the band's rows, or the board has constraints that
+    /// depend on which squares are next to each other.
+    pub fn permute_rows(&mut self, band: usize, permutation: &[usize]) -> bool {
+        let height = self.box_height;
+        if self.uses_adjacency()
+            || band >= self.size() / height
+            || permutation.len() != height
+            || !is_permutation(permutation)
+        {
+            return false;
+        }
+
+        self.rearrange(|(row, column)| {
+            if row / height == band {
+                (band * height + permutation[row % height], column)
+            } else {
+                (row, column)
+            }
+        });
+
+        true
+    }
+
+    /// Reorders the columns within one stack so that column `i` of the stack becomes the column
+    /// that was at `permutation[i]`. Returns false without changing anything if `stack` is out of
+    /// range, `permutation` is not a permutation of the stack's columns, or the board has
+    /// constraints that depend on which squares are next to each other.
+    pub fn permute_columns(&mut self, stack: usize, permutation: &[usize]) -> bool {
+        let width = self.box_width;
+        if self.uses_adjacency()
+            || stack >= self.size() / width
+            || permutation.len() != width
+            || !is_permutation(permutation)
+        {
+            return false;
+        }
+
+        self.rearrange(|(row, column)| {
+            if column / width == stack {
+                (row, stack * width + permutation[column % width])
+            } else {
+                (row, column)
+            }
+        });
+
+        true
+    }
+
+    /// Replaces every number `n` on the board, collapsed or not, with `mapping[n.index()]`.
+    /// Returns false without changing anything if `mapping` is not a permutation of the board's
+    /// numbers, or if the board has constraints such as cages that depend on the numbers' values.
+    pub fn relabel(&mut self, mapping: &[Number]) -> bool {
+        let indices: Vec<_> = mapping.iter().map(|number| number.index()).collect();
+        if indices.len() != self.size()
+            || !is_permutation(&indices)
+            || self.constraints.iter().any(Constraint::uses_values)
+        {
+            return false;
+        }
+
+        self.board
+            .iter_mut()
+            .flat_map(|row| row.iter_mut())
+            .for_each(|square| square.relabel(mapping));
+        for numbers in self.eliminated.iter_mut().flatten() {
+            numbers
+                .iter_mut()
+                .for_each(|number| *number = mapping[number.index()]);
+            numbers.sort_unstable();
+        }
+
+        true
+    }
+
+    /// Finds the representative of every board that can be reached from this one through
+    /// rotations, reflections, band, stack, row and column permutations and relabeling (unless the
+    /// board has constraints that depend on the numbers' values). Boards with constraints that
+    /// depend on which squares are next to each other, or that are drawn as shapes such as cages,
+    /// jigsaw regions and diagonals, are only rotated and reflected. Two classic boards are copies
+    /// of each other exactly when their canonical forms are equal.
+    ///
+    /// The representative is the board whose collapsed numbers, read from a1 along row 1 and up
+    /// to the last row with unsolved squares counted as zero, come first in lexicographic order,
+    /// with ties broken by the constraints beyond rows, columns and boxes after they are moved
+    /// along with the squares. Every symmetry is tried, which takes a moment for 9x9 boards, so
+    /// returns `None` for boards with more symmetries than a 9x9 board, such as 12x12 and 16x16.
+    pub fn canonicalize(&self) -> Option<Self> {
+        let size = self.size();
+        if !self.uses_adjacency()
+            && !matches!(self.symmetry_count(), Some(count) if count <= MAX_SYMMETRIES)
+        {
+            return None;
+        }
+
+        let (row_permutations, column_permutations) = if self.uses_adjacency() {
+            let forwards: Vec<_> = (0..size).collect();
+            let backwards = forwards.iter().rev().copied().collect();
+            let reflections = vec![forwards, backwards];
+
+            (reflections.clone(), reflections)
+        } else {
+            (
+                line_permutations(size / self.box_height, self.box_height),
+                line_permutations(size / self.box_width, self.box_width),
+            )
+        };
+        let transpositions: &[bool] = match self.box_height == self.box_width {
+            true => &[false, true],
+            false => &[false],
+        };
+
+        let relabeling = !self.constraints.iter().any(Constraint::uses_values);
+
+        let mut best: Option<(Vec<usize>, Arrangement)> = None;
+        let mut best_constraints: Option<Vec<Constraint>> = None;
+        let mut candidate = vec![0; size * size];
+        let mut labels = vec![0; size + 1];
+
+        for &transposed in transpositions {
+            let values = self.values(transposed);
+
+            for rows in &row_permutations {
+                for columns in &column_permutations {
+                    labels.fill(0);
+                    let mut next_label = 1;
+                    let mut ordering = match best {
+                        Some(_) => Ordering::Equal,
+                        None => Ordering::Less,
+                    };
+
+                    for (i, candidate_value) in candidate.iter_mut().enumerate() {
+                        let value = values[rows[i / size]][columns[i % size]];
+                        if !relabeling {
+                            labels[value] = value;
+                        } else if value != 0 && labels[value] == 0 {
+                            labels[value] = next_label;
+                            next_label += 1;
+                        }
+                        let label = labels[value];
+                        *candidate_value = label;
+
+                        if let (Ordering::Equal, Some((best_values, ..))) = (ordering, &best) {
+                            ordering = label.cmp(&best_values[i]);
+                            if ordering == Ordering::Greater {
+                                break;
+                            }
+                        }
+                    }
+
+                    let arrangement = Arrangement {
+                        transposed,
+                        rows: rows.clone(),
+                        columns: columns.clone(),
+                    };
+                    if let (Ordering::Equal, Some((_, best_arrangement))) = (ordering, &best) {
+                        let constraints = self.moved_constraints(&arrangement);
+                        ordering = constraints.cmp(
+                            best_constraints
+                                .get_or_insert_with(|| self.moved_constraints(best_arrangement)),
+                        );
+                        if ordering == Ordering::Less {
+                            best_constraints = Some(constraints);
+                        }
+                    } else if ordering == Ordering::Less {
+                        best_constraints = None;
+                    }
+
+                    if ordering == Ordering::Less {
+                        best = Some((candidate.clone(), arrangement));
+                    }
+                }
+            }
+        }
+
+        let (_values, arrangement) =
+            best.expect("There is always at least one arrangement of a board");
+
+        let mut canonical = self.clone();
+        if arrangement.transposed {
+            canonical.transpose();
+        }
+        canonical.rearrange(|(row, column)| (arrangement.rows[row], arrangement.columns[column]));
+        canonical.relabel(&canonical.first_appearance_labels());
+        canonical.constraints.sort_unstable();
+
+        Some(canonical)
+    }
+
+    /// How many ways bands, stacks, rows and columns can be reordered and the board transposed,
+    /// or `None` if there are too many to count.
+    fn symmetry_count(&self) -> Option<usize> {
+        let size = self.size();
+        let transpositions = match self.box_height == self.box_width {
+            true => 2,
+            false => 1,
+        };
+
+        line_permutation_count(size / self.box_height, self.box_height)?
+            .checked_mul(line_permutation_count(
+                size / self.box_width,
+                self.box_width,
+            )?)?
+            .checked_mul(transpositions)
+    }
+
+    /// The constraints in sorted order after moving their locations through `arrangement`, as
+    /// [`Board::rearrange`] would.
+    fn moved_constraints(&self, arrangement: &Arrangement) -> Vec<Constraint> {
+        let mut rows = vec![0; self.size()];
+        let mut columns = vec![0; self.size()];
+        for (index, &row) in arrangement.rows.iter().enumerate() {
+            rows[row] = index;
+        }
+        for (index, &column) in arrangement.columns.iter().enumerate() {
+            columns[column] = index;
+        }
+
+        let mut constraints = self.constraints.clone();
+        for constraint in &mut constraints {
+            constraint.map_locations(|(row, column)| match arrangement.transposed {
+                true => (rows[column], columns[row]),
+                false => (rows[row], columns[column]),
+            });
+        }
+        constraints.sort_unstable();
+
+        constraints
+    }
+
+    /// Whether any constraint depends on which squares are next to each other, in which case only
+    /// rotations and reflections give an equivalent board.
+    fn uses_adjacency(&self) -> bool {
+        self.constraints.iter().any(Constraint::uses_adjacency)
+    }
+
+    /// Maps each number to its order of first appearance on the board, with numbers that never
+    /// appear following in their natural order.
+    fn first_appearance_labels(&self) -> Vec<Number> {
+        let numbers = &Number::ALL[..self.size()];
+
+        let mut order: Vec<Number> = Vec::with_capacity(numbers.len());
+        for number in self
+            .board
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter_map(|square| square.collapsed_number())
+            .chain(numbers.iter().copied())
+        {
+            if !order.contains(&number) {
+                order.push(number);
+            }
+        }
+
+        let mut mapping = numbers.to_vec();
+        for (label, number) in order.into_iter().enumerate() {
+            mapping[number.index()] = Number::ALL[label];
+        }
+
+        mapping
+    }
+
+    /// Moves every square, along with the candidates ruled out there, so that the square at a
+    /// location comes from `source(location)`, and moves the locations in every constraint along
+    /// with them.
+    fn rearrange(&mut self, source: impl Fn((usize, usize)) -> (usize, usize)) {
+        let size = self.size();
+        let original = self.clone();
+        let mut destinations = vec![vec![(0, 0); size]; size];
+
+        for row in 0..size {
+            for column in 0..size {
+                let from = source((row, column));
+                *self.get_mut((row, column)) = original.get(from).clone();
+                self.eliminated[row][column] = original.eliminated[from.0][from.1].clone();
+                destinations[from.0][from.1] = (row, column);
+            }
+        }
+
+        for constraint in &mut self.constraints {
+            constraint.map_locations(|location| destinations[location.0][location.1]);
+        }
+    }
+
+    /// The collapsed numbers on the board by value, with 0 for unsolved squares.
+    fn values(&self, transposed: bool) -> Vec<Vec<usize>> {
+        let size = self.size();
+        let mut values = vec![vec![0; size]; size];
+
+        for (row, row_values) in values.iter_mut().enumerate() {
+            for (column, value) in row_values.iter_mut().enumerate() {
+                let location = match transposed {
+                    true => (column, row),
+                    false => (row, column),
+                };
+                if let Some(number) = self.get(location).collapsed_number() {
+                    *value = number.value();
+                }
+            }
+        }
+
+        values
+    }
+}
+
+/// The number of symmetries of a 9x9 board, the most [`Board::canonicalize`] will try.
+const MAX_SYMMETRIES: usize = 2 * 1296 * 1296;
+
+/// A symmetry of the board made of an optional transposition followed by reordering rows and
+/// columns.
+struct Arrangement {
+    transposed: bool,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+}
+
+/// Every ordering of `groups * group_length` rows (or columns) that keeps each band (or stack)
+/// together.
+fn line_permutations(groups: usize, group_length: usize) -> Vec<Vec<usize>> {
+    let group_orders = permutations(groups);
+    let within_orders = permutations(group_length);
+
+    let mut orderings = vec![Vec::with_capacity(groups * group_length)];
+    for group in 0..groups {
+        orderings = orderings
+            .into_iter()
+            .flat_map(|ordering| {
+                within_orders.iter().map(move |within| {
+                    let mut ordering = ordering.clone();
+                    ordering.extend(within.iter().map(|line| group * group_length + line));
+                    ordering
+                })
+            })
+            .collect();
+    }
+
+    group_orders
+        .iter()
+        .flat_map(|groups_order| {
+            orderings.iter().map(move |ordering| {
+                ordering
+                    .iter()
+                    .map(|line| {
+                        groups_order[line / group_length] * group_length + line % group_length
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// How many orderings [`line_permutations`] gives, or `None` if there are too many to count.
+fn line_permutation_count(groups: usize, group_length: usize) -> Option<usize> {
+    let within = factorial(group_length)?;
+
+    (0..groups).try_fold(factorial(groups)?, |count, _| count.checked_mul(within))
+}
+
+fn factorial(n: usize) -> Option<usize> {
+    (1..=n).try_fold(1usize, |product, factor| product.checked_mul(factor))
+}
+
+/// Every permutation of 0..length.
+fn permutations(length: usize) -> Vec<Vec<usize>> {
+    if length == 0 {
+        return vec![Vec::new()];
+    }
+
+    permutations(length - 1)
+        .into_iter()
+        .flat_map(|shorter| {
+            (0..length).map(move |position| {
+                let mut permutation = shorter.clone();
+                permutation.insert(position, length - 1);
+                permutation
+            })
+        })
+        .collect()
+}
+
+fn is_permutation(indices: &[usize]) -> bool {
+    let mut seen = vec![false; indices.len()];
+
+    indices
+        .iter()
+        .all(|&index| index < seen.len() && !std::mem::replace(&mut seen[index], true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn puzzle() -> Board {
+        let mut board = Board::default();
+        for (number, location) in [
+            (Number::Five, (0, 2)),
+            (Number::One, (0, 4)),
+            (Number::Three, (3, 7)),
+            (Number::Nine, (5, 0)),
+            (Number::Two, (6, 6)),
+            (Number::Seven, (8, 1)),
+        ] {
+            assert!(board.try_collapse(number, location));
+        }
+        board
+    }
+
+    #[test]
+    fn rotating_four_times_is_identity() {
+        let mut board = puzzle();
+        board.rotate();
+        assert_ne!(puzzle(), board);
+        assert_eq!(Some(Number::Five), board.get((6, 0)).collapsed_number());
+
+        board.rotate();
+        board.rotate();
+        board.rotate();
+        assert_eq!(puzzle(), board);
+    }
+
+    #[test]
+    fn rotating_turns_rectangular_boxes() {
+        let mut board = Board::new(2, 3).unwrap();
+        assert!(board.try_collapse(Number::Six, (0, 5)));
+
+        board.rotate();
+        assert_eq!((3, 2), (board.box_height(), board.box_width()));
+        assert_eq!(Some(Number::Six), board.get((0, 0)).collapsed_number());
+    }
+
+    #[test]
+    fn invalid_permutations_are_rejected() {
+        let mut board = puzzle();
+        assert!(!board.permute_bands(&[0, 0, 1]));
+        assert!(!board.permute_rows(3, &[0, 1, 2]));
+        assert!(!board.permute_columns(0, &[1, 0]));
+        assert!(!board.relabel(&[Number::One; 9]));
+        assert!(!board.relabel(&Number::ALL));
+        assert_eq!(puzzle(), board);
+
+        assert!(board.add_constraint(Constraint::AntiKnight));
+        assert!(!board.permute_rows(0, &[1, 0, 2]));
+        assert!(!board.permute_bands(&[2, 1, 0]));
+    }
+
+    #[test]
+    fn copies_share_a_canonical_form() {
+        let mut copy = puzzle();
+        copy.transpose();
+        copy.reflect_vertically();
+        copy.permute_stacks(&[2, 0, 1]);
+        copy.permute_columns(1, &[1, 2, 0]);
+        copy.permute_rows(2, &[2, 1, 0]);
+        copy.relabel(&[
+            Number::Nine,
+            Number::Three,
+            Number::One,
+            Number::Eight,
+            Number::Two,
+            Number::Seven,
+            Number::Four,
+            Number::Six,
+            Number::Five,
+        ]);
+
+        assert_eq!(puzzle().canonicalize(), copy.canonicalize());
+        assert!(puzzle().canonicalize().is_some());
+
+        let mut different = puzzle();
+        different.try_collapse(Number::Four, (4, 4));
+        assert_ne!(puzzle().canonicalize(), different.canonicalize());
+    }
+
+    #[test]
+    fn canonical_forms_keep_the_shapes_of_constraints() {
+        let mut board = puzzle();
+        for diagonal in Constraint::diagonals(9) {
+            assert!(board.add_constraint(diagonal));
+        }
+        assert!(!board.clone().permute_columns(0, &[1, 0, 2]));
+
+        // Band and stack permutations would bend the diagonals out of shape.
+        let canonical = board.canonicalize().unwrap();
+        assert_eq!(board.constraints.len(), canonical.constraints.len());
+        for constraint in &canonical.constraints {
+            if let Constraint::Diagonal(locations) = constraint {
+                assert!(locations
+                    .iter()
+                    .all(|&(row, column)| row == column || row + column == 8));
+            }
+        }
+    }
+
+    #[test]
+    fn variant_copies_share_a_canonical_form() {
+        // With no numbers placed, only the cage tells the arrangements apart.
+        let cage = |sum, locations: &[(usize, usize)]| {
+            let mut board = Board::default();
+            assert!(board.add_constraint(Constraint::Cage {
+                sum,
+                locations: locations.to_vec(),
+            }));
+            board
+        };
+        let board = cage(3, &[(0, 0), (0, 1)]);
+        let mut copy = board.clone();
+        copy.rotate();
+        copy.reflect_horizontally();
+
+        assert_eq!(board.canonicalize(), copy.canonicalize());
+        assert_eq!(
+            board.canonicalize(),
+            cage(3, &[(7, 8), (8, 8)]).canonicalize()
+        );
+        assert_ne!(
+            board.canonicalize(),
+            cage(4, &[(7, 8), (8, 8)]).canonicalize()
+        );
+    }
+
+    #[test]
+    fn boards_with_too_many_symmetries_are_refused() {
+        assert_eq!(None, Board::new(4, 4).unwrap().canonicalize());
+        assert_eq!(None, Board::new(3, 4).unwrap().canonicalize());
+        assert!(Board::new(2, 3).unwrap().canonicalize().is_some());
+    }
+}
@@ -26,6 +26,174 @@ fn starting_game_representation_looks_right() {
     assert_eq!(correct_display, format!("{}", Board::default()));
 }
 
+#[test]
+fn smaller_boards_look_right() {
+    let mut board = Board::new(2, 3).unwrap();
+    board.try_collapse(Number::Six, (0, 5));
+
+    assert_eq!(
+        [
+            "                    \n",
+            "  |-------|-------| \n",
+            "6 | ? ? ? | ? ? ? | \n",
+            "5 | ? ? ? | ? ? ? | \n",
+            "  |-------|-------| \n",
+            "4 | ? ? ? | ? ? ? | \n",
+            "3 | ? ? ? | ? ? ? | \n",
+            "  |-------|-------| \n",
+            "2 | ? ? ? | ? ? ? | \n",
+            "1 | ? ? ? | ? ? 6 | \n",
+            "  |-------|-------| \n",
+            "    a b c   d e f   \n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
+#[test]
+fn larger_boards_look_right() {
+    let mut board = Board::new(4, 4).unwrap();
+    board.try_collapse(Number::Sixteen, (15, 0));
+    let display = format!("{board}");
+    let mut lines = display.lines().skip(1);
+
+    assert_eq!(
+        Some("   |---------|---------|---------|---------| "),
+        lines.next()
+    );
+    assert_eq!(
+        Some("16 | G ? ? ? | ? ? ? ? | ? ? ? ? | ? ? ? ? | "),
+        lines.next()
+    );
+    assert_eq!(
+        Some("     a b c d   e f g h   i j k l   m n o p   "),
+        display.lines().last()
+    );
+}
+
+#[test]
+fn diagonals_are_marked() {
+    let mut board = Board::default();
+    for diagonal in Constraint::diagonals(9) {
+        assert!(board.add_constraint(diagonal));
+    }
+    board.try_collapse(Number::Five, (4, 4));
+
+    assert_eq!(
+        [
+            "                            \n",
+            "  |-------|-------|-------| \n",
+            "9 | ?\\? ? | ? ? ? | ? ? ?/| \n",
+            "8 | ? ?\\? | ? ? ? | ? ?/? | \n",
+            "7 | ? ? ?\\| ? ? ? | ?/? ? | \n",
+            "  |-------|-------|-------| \n",
+            "6 | ? ? ? | ?\\? ?/| ? ? ? | \n",
+            "5 | ? ? ? | ? 5X? | ? ? ? | \n",
+            "4 | ? ? ? | ?/? ?\\| ? ? ? | \n",
+            "  |-------|-------|-------| \n",
+            "3 | ? ? ?/| ? ? ? | ?\\? ? | \n",
+            "2 | ? ?/? | ? ? ? | ? ?\\? | \n",
+            "1 | ?/? ? | ? ? ? | ? ? ?\\| \n",
+            "  |-------|-------|-------| \n",
+            "    a b c   d e f   g h i   \n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
+#[test]
+fn cages_are_outlined() {
+    let mut board = Board::new(2, 2).unwrap();
+    for (sum, locations) in [
+        (3, vec![(3, 0), (3, 1)]),
+        (7, vec![(2, 0), (2, 1)]),
+        (10, vec![(0, 1), (0, 2), (0, 3), (1, 2)]),
+    ] {
+        assert!(board.add_constraint(Constraint::Cage { sum, locations }));
+    }
+    board.try_collapse(Number::One, (3, 0));
+
+    assert_eq!(
+        [
+            "\n",
+            "  +3--+---+---+---+\n",
+            "4 | 1   ! | ?   ? |\n",
+            "  +7......+       +\n",
+            "3 | ?   ? | ?   ? |\n",
+            "  +---+---+10-+---+\n",
+            "2 | ?   ? | ? : ? |\n",
+            "  +   ....+   ....+\n",
+            "1 | ? : ? | ?   ? |\n",
+            "  +---+---+---+---+\n",
+            "    a   b   c   d\n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
+#[test]
+fn irregular_boxes_are_outlined() {
+    let mut board = Board::new(2, 2).unwrap();
+    assert!(board.replace_boxes(vec![
+        vec![(3, 0), (3, 1), (3, 2), (2, 1)],
+        vec![(3, 3), (2, 2), (2, 3), (1, 3)],
+        vec![(2, 0), (1, 0), (1, 1), (0, 0)],
+        vec![(1, 2), (0, 1), (0, 2), (0, 3)],
+    ]));
+    board.try_collapse(Number::One, (3, 2));
+
+    assert_eq!(
+        [
+            "\n",
+            "  +---+---+---+---+\n",
+            "4 | ?   ?   1 | ? |\n",
+            "  +---+   +---+   +\n",
+            "3 | ? | ? | ?   ? |\n",
+            "  +   +---+---+   +\n",
+            "2 | ?   ? | ? | ? |\n",
+            "  +   +---+   +---+\n",
+            "1 | ? | ?   ?   ? |\n",
+            "  +---+---+---+---+\n",
+            "    a   b   c   d\n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
+#[test]
+fn windows_are_marked() {
+    let mut board = Board::default();
+    for window in Constraint::windows(3, 3) {
+        assert!(board.add_constraint(window));
+    }
+
+    assert_eq!(
+        [
+            "                            \n",
+            "  |-------|-------|-------| \n",
+            "9 | ? ? ? | ? ? ? | ? ? ? | \n",
+            "8 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "7 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "  |-------|-------|-------| \n",
+            "6 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "5 | ? ? ? | ? ? ? | ? ? ? | \n",
+            "4 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "  |-------|-------|-------| \n",
+            "3 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "2 | ? ?+?+| ?+? ?+| ?+?+? | \n",
+            "1 | ? ? ? | ? ? ? | ? ? ? | \n",
+            "  |-------|-------|-------| \n",
+            "    a b c   d e f   g h i   \n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
 #[test]
 fn game_in_progress_looks_right() {
     let mut board = Board::default();
@@ -145,3 +313,109 @@ fn to_coordinate(coordinate: u8) -> usize {
         _ => panic!("Failed to create coordinate."),
     }
 }
+
+#[test]
+fn outside_clues_sit_in_a_margin() {
+    let mut board = Board::new(2, 2).unwrap();
+    assert!(board.add_constraint(Constraint::Sandwich {
+        sum: 5,
+        locations: (0..4).rev().map(|row| (row, 1)).collect(),
+    }));
+    assert!(board.add_constraint(Constraint::Skyscraper {
+        count: 2,
+        locations: (0..4).map(|column| (2, column)).collect(),
+    }));
+    assert!(board.add_constraint(Constraint::Skyscraper {
+        count: 3,
+        locations: (0..4).rev().map(|column| (0, column)).collect(),
+    }));
+    assert!(board.add_constraint(Constraint::LittleKiller {
+        sum: 5,
+        locations: vec![(0, 2), (1, 3)],
+    }));
+
+    assert_eq!(
+        [
+            "                  \n",
+            "        5\n",
+            "    |-----|-----| \n",
+            "  4 | ? ? | ? ? | \n",
+            "2 3 | ? ? | ? ? | \n",
+            "    |-----|-----| \n",
+            "  2 | ? ? | ? ? | \n",
+            "  1 | ? ? | ? ? | 3\n",
+            "    |-----|-----| \n",
+            "        5\n",
+            "        /\n",
+            "      a b   c d   \n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}
+
+#[test]
+fn overlapping_boards_look_right() {
+    let mut twodoku = MultiBoard::twodoku();
+    assert!(twodoku.try_collapse(Number::Seven, (7, 7)));
+    assert_eq!(
+        [
+            "\n",
+            "   |-------|-------|-------|\n",
+            "15 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "14 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "13 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "   |-------|-------|-------|\n",
+            "12 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "11 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "10 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "   |-------|-------|-------|-------|-------|\n",
+            " 9 | ? ? ? | ? ? ? | ? ? ? | ? ? ? | ? ? ? |\n",
+            " 8 | ? ? ? | ? ? ? | ? 7 ? | ? ? ? | ? ? ? |\n",
+            " 7 | ? ? ? | ? ? ? | ? ? ? | ? ? ? | ? ? ? |\n",
+            "   |-------|-------|-------|-------|-------|\n",
+            " 6                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            " 5                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            " 4                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "                   |-------|-------|-------|\n",
+            " 3                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            " 2                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            " 1                 | ? ? ? | ? ? ? | ? ? ? |\n",
+            "                   |-------|-------|-------|\n",
+            "     a b c   d e f   g h i   j k l   m n o\n",
+        ]
+        .concat(),
+        format!("{twodoku}")
+    );
+}
+
+#[test]
+fn inequalities_and_parity_are_marked() {
+    let mut board = Board::new(2, 2).unwrap();
+    assert!(board.add_constraint(Constraint::GreaterThan {
+        greater: (3, 0),
+        lesser: (3, 1),
+    }));
+    assert!(board.add_constraint(Constraint::GreaterThan {
+        greater: (1, 2),
+        lesser: (2, 2),
+    }));
+    assert!(board.add_constraint(Constraint::Even(vec![(0, 0)])));
+    assert!(board.add_constraint(Constraint::Odd(vec![(0, 3)])));
+
+    assert_eq!(
+        [
+            "                \n",
+            "  |-----|-----| \n",
+            "4 | ?>? | ? ? | \n",
+            "3 | ? ? | ?^? | \n",
+            "  |-----|-----| \n",
+            "2 | ? ? | ? ? | \n",
+            "1 | ?#? | ? ?o| \n",
+            "  |-----|-----| \n",
+            "    a b   c d   \n",
+        ]
+        .concat(),
+        format!("{board}")
+    );
+}